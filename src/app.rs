@@ -12,17 +12,29 @@ use crate::{
         swing_list::swing_list,
         window::*,
     },
+    bounds::recompute_meshex_bounds,
     capture::{render_animation_to_gif, render_animation_to_image_sequence, render_screenshot},
     editors::{
-        Editor,
+        Editor, find_file,
         matl::{matl_editor, preset_editor},
+        mesh::auto_number_subindices,
+        meshex::match_meshex_order_to_mesh,
+        modl::match_modl_order_to_mesh,
         nutexb::nutexb_viewer,
+        swing::swing_editor,
     },
     load_model,
     log::AppLogger,
+    material::MaterialVariantRule,
     model_folder::ModelFolderState,
+    modl::{MaterialAssignmentRule, new_modl},
     path::{folder_display_name, folder_editor_title, last_update_check_file},
     preferences::AppPreferences,
+    presets::{MaterialPreset, PresetCategory},
+    texture::{
+        ChannelStatistics, EXPORT_EXTENSIONS, IMPORT_EXTENSIONS, assigned_texture_param,
+        default_import_format, export_all_nutexbs,
+    },
     thumbnail::generate_model_thumbnails,
     update::LatestReleaseInfo,
     update_color_theme,
@@ -35,16 +47,18 @@ use egui::{
 use egui_commonmark::CommonMarkCache;
 use egui_wgpu::{CallbackResources, CallbackTrait, ScreenDescriptor};
 use log::error;
+use nutexb::NutexbFormat;
 use once_cell::sync::Lazy;
 use rfd::FileDialog;
 use ssbh_data::prelude::*;
 use ssbh_data::{adj_data::AdjEntryData, matl_data::MatlEntryData};
 use ssbh_wgpu::{RenderModel, next_frame};
 use std::{
-    collections::{HashSet, VecDeque},
+    collections::{BTreeSet, HashMap, HashSet, VecDeque},
     path::{Path, PathBuf},
     sync::Mutex,
 };
+use xmb_lib::XmbFile;
 
 mod anim_list;
 mod animation_bar;
@@ -107,8 +121,10 @@ pub struct SsbhApp {
     pub animation_gif_to_render: Option<PathBuf>,
     pub animation_image_sequence_to_render: Option<PathBuf>,
 
-    pub material_presets: Vec<MatlEntryData>,
-    pub default_presets: Vec<MatlEntryData>,
+    pub material_presets: Vec<MaterialPreset>,
+    pub default_presets: Vec<MaterialPreset>,
+
+    pub material_variant_rules: Vec<MaterialVariantRule>,
 
     pub red_checkerboard: egui::TextureId,
     pub yellow_checkerboard: egui::TextureId,
@@ -160,6 +176,7 @@ pub struct UiState {
     pub log_window_open: bool,
     pub preferences_window_open: bool,
     pub device_info_window_open: bool,
+    pub model_statistics_window_open: bool,
 
     // TODO: Is there a better way to track this?
     // Clicking an item in the file list sets the selected index.
@@ -175,9 +192,12 @@ pub struct UiState {
     pub open_adj: Option<usize>,
     pub open_anim: Option<usize>,
     pub open_meshex: Option<usize>,
+    pub open_xmb: Option<usize>,
 
     pub matl_editor: MatlEditorState,
     pub preset_editor: MatlEditorState,
+    /// Copied materials or parameters shared between Matl Editors for all folders.
+    pub material_clipboard: Option<MatlEntryData>,
     pub anim_editor: AnimEditorState,
     pub skel_editor: SkelEditorState,
     pub modl_editor: ModlEditorState,
@@ -185,10 +205,16 @@ pub struct UiState {
     pub nutexb: NutexbViewerState,
     pub hlpb_editor: HlpbEditorState,
     pub mesh_editor: MeshEditorState,
+    pub texture_import: Option<TextureImportState>,
+    pub unused_textures: Option<UnusedTexturesState>,
+    /// The index of the model folder with the swing.prc to edit.
+    pub open_swing_prc: Option<usize>,
 }
 
 pub struct NutexbViewerState {
     pub rect: egui::Rect,
+    /// Statistics for the title, layer, and mipmap of the last viewed texture.
+    pub channel_statistics: Option<((String, u32, u32), Option<ChannelStatistics>)>,
 }
 
 impl Default for NutexbViewerState {
@@ -198,6 +224,7 @@ impl Default for NutexbViewerState {
                 min: egui::Pos2 { x: 0.0, y: 0.0 },
                 max: egui::Pos2 { x: 512.0, y: 512.0 },
             },
+            channel_statistics: None,
         }
     }
 }
@@ -214,7 +241,7 @@ pub enum SkelMode {
     Hierarchy,
 }
 
-#[derive(PartialEq, Eq, Default)]
+#[derive(PartialEq, Eq, Clone, Copy, Default)]
 pub enum PresetMode {
     User,
     #[default]
@@ -224,8 +251,14 @@ pub enum PresetMode {
 #[derive(Default)]
 pub struct MatlEditorState {
     pub selected_material_index: usize,
+    /// All materials selected with ctrl or shift click for batch editing.
+    pub selected_material_indices: BTreeSet<usize>,
+    pub batch_add_missing: bool,
     pub hovered_material_index: Option<usize>,
     pub texture_to_edit_index: Option<usize>,
+    pub material_label_before_edit: Option<String>,
+    /// The old and new label after the user finishes renaming a material.
+    pub renamed_material_label: Option<(String, String)>,
 
     pub matl_preset_window_open: bool,
     pub preset_mode: PresetMode,
@@ -233,11 +266,46 @@ pub struct MatlEditorState {
 
     pub shader_finder_window_open: bool,
     pub shader_finder: ShaderFinderState,
+
+    pub compare_window_open: bool,
+    pub compare: MatlCompareState,
+
+    pub auto_assign_window_open: bool,
+    pub auto_assign_all_materials: bool,
+
+    pub usage_window_open: bool,
+
+    pub preset_search: String,
+    pub preset_category: Option<PresetCategory>,
+    /// The preset being named before adding it to the user presets.
+    pub new_preset: Option<MaterialPreset>,
+    pub new_preset_tag: String,
+    pub preset_thumbnails: HashMap<String, Option<egui::TextureHandle>>,
+}
+
+#[derive(Default)]
+pub struct MatlCompareState {
+    pub source: MatlCompareSource,
+    pub entry_index: usize,
+    pub differences_only: bool,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Default)]
+pub enum MatlCompareSource {
+    /// The numatb file open in the Matl Editor.
+    #[default]
+    CurrentFile,
+    /// The numatb file at the index in the list of numatb files from other folders.
+    OtherFile(usize),
+    Preset(PresetMode),
 }
 
 pub struct ShaderFinderState {
     pub selected_shader: String,
 
+    /// Only show shaders with required attributes present in this mesh object.
+    pub mesh_object_index: Option<usize>,
+
     // TODO: Store just the used params instead?
     pub has_float: [bool; 20],
     pub has_boolean: [bool; 20],
@@ -272,6 +340,7 @@ impl Default for ShaderFinderState {
     fn default() -> Self {
         Self {
             selected_shader: Default::default(),
+            mesh_object_index: None,
             has_float: Default::default(),
             has_boolean: Default::default(),
             has_texture: Default::default(),
@@ -304,12 +373,14 @@ impl Default for ShaderFinderState {
 #[derive(Default)]
 pub struct ModlEditorState {
     pub editor_tab: ModlEditorTab,
+    pub material_rules: Vec<MaterialAssignmentRule>,
 }
 
 #[derive(PartialEq, Eq, Default)]
 pub enum ModlEditorTab {
     #[default]
     Assignments,
+    Rules,
     Files,
 }
 
@@ -330,6 +401,8 @@ pub enum HlpbEditorTab {
 #[derive(Default)]
 pub struct MeshEditorState {
     pub selected_index: usize,
+    pub attribute_fill_value: [f32; 4],
+    pub match_entry_order: bool,
 }
 
 #[derive(Default)]
@@ -341,6 +414,22 @@ pub struct StageLightingState {
     pub stage_shpc: Option<PathBuf>,
 }
 
+pub struct TextureImportState {
+    pub folder_index: usize,
+    pub image_path: PathBuf,
+    /// The nutexb file name without the extension.
+    pub name: String,
+    pub format: NutexbFormat,
+    /// `true` if the format was selected manually instead of based on the name.
+    pub is_format_edited: bool,
+}
+
+pub struct UnusedTexturesState {
+    pub folder_index: usize,
+    /// Files selected for deletion that still need to be confirmed.
+    pub files_to_delete: Vec<String>,
+}
+
 #[derive(PartialEq, Eq, Default)]
 pub enum AnimEditorTab {
     #[default]
@@ -696,6 +785,54 @@ impl SsbhApp {
 
         log_window(ctx, &mut self.ui_state.log_window_open);
 
+        if let Some(folder_index) =
+            texture_import_window(ctx, &mut self.ui_state.texture_import, &mut self.models)
+        {
+            self.render_actions
+                .push_back(RenderAction::Model(RenderModelAction::Update(folder_index)));
+            self.should_update_thumbnails = true;
+            self.should_validate_models = true;
+        }
+
+        if let Some(folder_index) =
+            unused_textures_window(ctx, &mut self.ui_state.unused_textures, &mut self.models)
+        {
+            // Indices for the remaining files may have changed.
+            if self.ui_state.selected_folder_index == Some(folder_index) {
+                self.ui_state.open_nutexb = None;
+            }
+            self.render_actions
+                .push_back(RenderAction::Model(RenderModelAction::Update(folder_index)));
+            self.should_update_thumbnails = true;
+            self.should_validate_models = true;
+        }
+
+        if let Some(folder_index) = self.ui_state.open_swing_prc
+            && let Some(model) = self.models.get_mut(folder_index)
+            && let Some(swing_prc) = model.swing_prc.as_mut()
+        {
+            let response = swing_editor(ctx, &model.folder_path, swing_prc);
+            response.set_changed(&mut model.changed.swing_prc);
+
+            if response.changed {
+                // Preview the new collision shapes in the viewport.
+                self.swing_state.should_update_swing = true;
+            }
+
+            if !response.open {
+                self.ui_state.open_swing_prc = None;
+            }
+        }
+
+        if model_statistics_window(
+            ctx,
+            &mut self.ui_state.model_statistics_window_open,
+            &self.models,
+            &mut self.preferences.budgets,
+        ) {
+            self.should_validate_models = true;
+        }
+
         if preferences_window(
             ctx,
             &mut self.preferences,
@@ -732,13 +869,31 @@ impl SsbhApp {
 
         // TODO: use open_editor for matl as well?
         // The functions would take an additional ui parameter.
+        // Split the folders to compare materials with other folders in the Matl Editor.
         if let Some(folder_index) = self.ui_state.selected_folder_index
-            && let Some(model) = self.models.get_mut(folder_index)
+            && folder_index < self.models.len()
+            && let (models_before, [model, models_after @ ..]) =
+                self.models.split_at_mut(folder_index)
         {
             // TODO: Group added state and implement the Editor trait.
             if let Some(matl_index) = self.ui_state.open_matl
                 && let Some((name, Some(matl))) = model.model.matls.get_mut(matl_index)
             {
+                // Only list other files when needed to avoid allocating every frame.
+                let other_matls: Vec<_> = if self.ui_state.matl_editor.compare_window_open {
+                    models_before
+                        .iter()
+                        .chain(models_after.iter())
+                        .flat_map(|m| {
+                            m.model.matls.iter().filter_map(move |(name, matl)| {
+                                Some((format!("{}/{name}", folder_display_name(m)), matl.as_ref()?))
+                            })
+                        })
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+
                 let response = matl_editor(
                     ctx,
                     &model.folder_path,
@@ -746,6 +901,8 @@ impl SsbhApp {
                     &mut self.ui_state.matl_editor,
                     matl,
                     find_file_mut(&mut model.model.modls, "model.numdlb"),
+                    find_file(&model.model.meshes, "model.numshb"),
+                    &model.model.anims,
                     model
                         .validation
                         .matl_errors
@@ -757,6 +914,10 @@ impl SsbhApp {
                     render_state.shared_data.database(),
                     &mut self.material_presets,
                     &self.default_presets,
+                    &other_matls,
+                    &mut self.ui_state.material_clipboard,
+                    &mut self.preferences.texture_suffix_rules,
+                    &self.material_variant_rules,
                     self.red_checkerboard,
                     self.yellow_checkerboard,
                     self.preferences.dark_mode,
@@ -886,18 +1047,54 @@ impl SsbhApp {
                 file_changed = true;
             }
 
+            if open_editor::<XmbFile>(
+                ctx,
+                model,
+                &mut self.ui_state.open_xmb,
+                &mut (),
+                &mut self.render_actions,
+                folder_index,
+                self.preferences.dark_mode,
+            ) {
+                // LOD settings require reloading the render model.
+                self.render_actions
+                    .push_back(RenderAction::Model(RenderModelAction::Update(folder_index)));
+                file_changed = true;
+            }
+
             if let Some(nutexb_index) = self.ui_state.open_nutexb
-                && let Some((name, Some(nutexb))) = model.model.nutexbs.get(nutexb_index)
-                && !nutexb_viewer(
+                && let Some((name, Some(nutexb))) = model.model.nutexbs.get_mut(nutexb_index)
+            {
+                let response = nutexb_viewer(
                     ctx,
                     &folder_editor_title(&model.folder_path, name),
+                    &model.folder_path,
+                    name,
                     &mut self.ui_state.nutexb,
                     nutexb,
+                    model
+                        .validation
+                        .nutexb_errors
+                        .get(&nutexb_index)
+                        .map(|e| e.as_slice())
+                        .unwrap_or_default(),
+                    &model.model.matls,
                     &mut render_state.texture_render_settings,
-                )
-            {
-                // Close the window.
-                self.ui_state.open_nutexb = None;
+                );
+                response.set_changed(&mut model.changed.nutexbs[nutexb_index]);
+
+                if response.changed {
+                    // Recreate the GPU textures for the viewport and texture preview.
+                    self.render_actions
+                        .push_back(RenderAction::Model(RenderModelAction::Update(folder_index)));
+                    self.should_update_thumbnails = true;
+                    file_changed = true;
+                }
+
+                if !response.open {
+                    // Close the window.
+                    self.ui_state.open_nutexb = None;
+                }
             }
         }
 
@@ -981,6 +1178,24 @@ impl SsbhApp {
                                 self.should_validate_models = true;
                             }
 
+                            // Prevent adding a file that already exists.
+                            let should_add_modl = model.model.find_mesh().is_some()
+                                && model.model.find_modl().is_none();
+
+                            if ui
+                                .add_enabled(should_add_modl, Button::new("Add model.numdlb"))
+                                .on_hover_text(
+                                    "Create a model.numdlb with an entry for each mesh object. Materials are assigned by matching mesh names with material labels.",
+                                )
+                                .clicked()
+                            {
+                                add_new_numdlb(model);
+                                self.render_actions.push_back(RenderAction::Model(
+                                    RenderModelAction::Update(folder_index),
+                                ));
+                                self.should_validate_models = true;
+                            }
+
                             // Prevent adding a file that already exists.
                             let mesh = model.model.find_mesh();
                             let should_add_meshex =
@@ -1000,6 +1215,101 @@ impl SsbhApp {
                                 model.changed.meshexes.push(true);
                             }
 
+                            if ui
+                                .add_enabled(
+                                    model.model.find_mesh().is_some(),
+                                    Button::new("Recompute Bounds"),
+                                )
+                                .on_hover_text(
+                                    "Recalculate the bounding spheres in the numshexb and numshb files from the current vertices.",
+                                )
+                                .clicked()
+                            {
+                                recompute_bounds(model);
+                                self.should_validate_models = true;
+                            }
+
+                            let mesh_index = model
+                                .model
+                                .meshes
+                                .iter()
+                                .position(|(f, _)| f == "model.numshb");
+                            if ui
+                                .add_enabled(
+                                    mesh_index.is_some(),
+                                    Button::new("Auto-number Subindices"),
+                                )
+                                .on_hover_text(
+                                    "Renumber subindices for each mesh name and update the model.numdlb and model.numshexb.",
+                                )
+                                .clicked()
+                                && let Some(mesh_index) = mesh_index
+                            {
+                                auto_number_model_subindices(model, mesh_index);
+                                self.render_actions.push_back(RenderAction::Model(
+                                    RenderModelAction::Update(folder_index),
+                                ));
+                                self.should_validate_models = true;
+                            }
+
+                            if ui
+                                .button("Import Image...")
+                                .on_hover_text(
+                                    "Convert a PNG, TGA, or DDS image to a new or existing nutexb in this folder.",
+                                )
+                                .clicked()
+                                && let Some(file) = FileDialog::new()
+                                    .add_filter("Image", &IMPORT_EXTENSIONS)
+                                    .pick_file()
+                            {
+                                let name = file
+                                    .file_stem()
+                                    .map(|s| s.to_string_lossy().to_string())
+                                    .unwrap_or_default();
+                                let param_id = assigned_texture_param(&model.model.matls, &name);
+                                self.ui_state.texture_import = Some(TextureImportState {
+                                    folder_index,
+                                    image_path: file,
+                                    name,
+                                    format: default_import_format(param_id),
+                                    is_format_edited: false,
+                                });
+                            }
+
+                            if ui
+                                .add_enabled(
+                                    !model.model.nutexbs.is_empty(),
+                                    Button::new("Unused Textures"),
+                                )
+                                .on_hover_text(
+                                    "List the nutexb files not assigned to any materials in this folder.",
+                                )
+                                .clicked()
+                            {
+                                self.ui_state.unused_textures = Some(UnusedTexturesState {
+                                    folder_index,
+                                    files_to_delete: Vec::new(),
+                                });
+                            }
+
+                            ui.add_enabled_ui(!model.model.nutexbs.is_empty(), |ui| {
+                                ui.menu_button("Export All Textures", |ui| {
+                                    for extension in EXPORT_EXTENSIONS {
+                                        if ui.button(extension.to_uppercase()).clicked()
+                                            && let Some(folder) = FileDialog::new()
+                                                .set_directory(&model.folder_path)
+                                                .pick_folder()
+                                        {
+                                            export_all_nutexbs(
+                                                &model.model.nutexbs,
+                                                &folder,
+                                                extension,
+                                            );
+                                        }
+                                    }
+                                });
+                            });
+
                             ui.separator();
 
                             // Use "Remove" since this doesn't delete the folder on disk.
@@ -1281,6 +1591,13 @@ fn open_editor<T: Editor>(
                             },
                         ));
                     }
+                    crate::EditorMessage::AutoNumberSubindices => {
+                        // Only the Mesh Editor sends this message, so the index is for the mesh.
+                        auto_number_model_subindices(model, *index);
+                    }
+                    crate::EditorMessage::MatchMeshOrder => {
+                        match_model_mesh_order(model);
+                    }
                 }
             }
         }
@@ -1469,6 +1786,110 @@ fn add_new_adjb(model: &mut ModelFolderState) {
     model.changed.adjs.push(true);
 }
 
+fn add_new_numdlb(model: &mut ModelFolderState) {
+    if let Some(mesh) = model.model.find_mesh() {
+        let skel_file_names: Vec<_> = model.model.skels.iter().map(|(f, _)| f.as_str()).collect();
+        let modl = new_modl(mesh, &skel_file_names, &model.model.matls);
+        model
+            .model
+            .modls
+            .push(("model.numdlb".to_owned(), Some(modl)));
+
+        // Mark the new file as modified to prompt the user to save it.
+        model.changed.modls.push(true);
+    }
+}
+
+/// Renumber subindices for the mesh at `mesh_index` and update the folder's modl and meshex.
+fn auto_number_model_subindices(model: &mut ModelFolderState, mesh_index: usize) {
+    let modl_index = model
+        .model
+        .modls
+        .iter()
+        .position(|(f, _)| f == "model.numdlb");
+    let meshex_index = model
+        .model
+        .meshexes
+        .iter()
+        .position(|(f, _)| f == "model.numshexb");
+
+    if let Some((_, Some(mesh))) = model.model.meshes.get_mut(mesh_index) {
+        auto_number_subindices(
+            mesh,
+            modl_index.and_then(|i| model.model.modls[i].1.as_mut()),
+            meshex_index.and_then(|i| model.model.meshexes[i].1.as_mut()),
+        );
+
+        // Mark all the modified files to prompt the user to save them.
+        model.changed.meshes[mesh_index] = true;
+        if let Some(i) = modl_index {
+            model.changed.modls[i] = true;
+        }
+        if let Some(i) = meshex_index {
+            model.changed.meshexes[i] = true;
+        }
+    }
+}
+
+fn match_model_mesh_order(model: &mut ModelFolderState) {
+    let Some(mesh) = model
+        .model
+        .meshes
+        .iter()
+        .find(|(f, _)| f == "model.numshb")
+        .and_then(|(_, m)| m.as_ref())
+    else {
+        return;
+    };
+
+    for ((f, modl), changed) in model.model.modls.iter_mut().zip(&mut model.changed.modls) {
+        if f == "model.numdlb"
+            && let Some(modl) = modl
+        {
+            match_modl_order_to_mesh(modl, mesh);
+            *changed = true;
+        }
+    }
+
+    for ((f, meshex), changed) in model
+        .model
+        .meshexes
+        .iter_mut()
+        .zip(&mut model.changed.meshexes)
+    {
+        if f == "model.numshexb"
+            && let Some(meshex) = meshex
+        {
+            match_meshex_order_to_mesh(meshex, mesh);
+            *changed = true;
+        }
+    }
+}
+
+fn recompute_bounds(model: &mut ModelFolderState) {
+    let Some((mesh_index, mesh)) = model
+        .model
+        .meshes
+        .iter()
+        .enumerate()
+        .find(|(_, (f, _))| f == "model.numshb")
+        .and_then(|(i, (_, m))| Some((i, m.as_ref()?)))
+    else {
+        return;
+    };
+
+    for (i, (_, meshex)) in model.model.meshexes.iter_mut().enumerate() {
+        if let Some(meshex) = meshex {
+            recompute_meshex_bounds(meshex, mesh);
+            model.changed.meshexes[i] = true;
+        }
+    }
+
+    // The mesh bounding data is recalculated when saving.
+    // Mark the mesh as modified to prompt the user to save it.
+    model.changed.meshes[mesh_index] = true;
+}
+
 fn find_file_mut<'a, T>(files: &'a mut [(String, FileResult<T>)], name: &str) -> Option<&'a mut T> {
     files
         .iter_mut()
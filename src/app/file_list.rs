@@ -112,6 +112,17 @@ pub fn show_folder_files(
         &model.validation.meshex_errors,
         |ui| mesh_icon(ui, dark_mode),
     );
+    list_files(
+        ui,
+        &model.model.xmbs,
+        &model.changed.xmbs,
+        folder_index,
+        &mut ui_state.selected_folder_index,
+        &mut ui_state.open_xmb,
+        None,
+        &model.validation.xmb_errors,
+        |ui| mesh_icon(ui, dark_mode),
+    );
     // TODO: Modify this to use the same function as above.
     list_nutexb_files(
         ui,
@@ -166,6 +177,10 @@ fn list_nutexb_files(
                 *selected_folder_index = Some(folder_index);
                 *selected_file_index = Some(i);
             }
+
+            if let Some(true) = model.changed.nutexbs.get(i) {
+                ui.label("[Modified]");
+            }
         });
     }
 }
@@ -42,7 +42,7 @@ impl SsbhApp {
         if self.should_validate_models {
             // Folders can be validated independently from one another.
             for model in &mut self.models {
-                model.validate(&render_state.shared_data)
+                model.validate(&render_state.shared_data, &self.preferences.budgets)
             }
             self.should_validate_models = false;
         }
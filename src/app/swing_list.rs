@@ -42,6 +42,20 @@ pub fn swing_list(app: &mut SsbhApp, ui: &mut Ui) {
                                 ui.make_persistent_id("swingcombo").with(i),
                                 prc_index,
                             );
+
+                            if let Some(prc_index) = prc_index {
+                                if ui.button("Edit").clicked() {
+                                    app.ui_state.open_swing_prc = Some(*prc_index);
+                                }
+
+                                if app
+                                    .models
+                                    .get(*prc_index)
+                                    .is_some_and(|m| m.changed.swing_prc)
+                                {
+                                    ui.label("[Modified]");
+                                }
+                            }
                         }
                     });
 
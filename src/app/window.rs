@@ -1,15 +1,21 @@
 mod camera;
 mod device_info;
 mod log;
+mod model_statistics;
 mod new_release;
 mod preferences;
 mod render_settings;
 mod stage_lighting;
+mod texture_import;
+mod unused_textures;
 
 pub use self::log::log_window;
 pub use camera::camera_settings_window;
 pub use device_info::device_info_window;
+pub use model_statistics::model_statistics_window;
 pub use new_release::new_release_window;
 pub use preferences::preferences_window;
 pub use render_settings::render_settings_window;
 pub use stage_lighting::stage_lighting_window;
+pub use texture_import::texture_import_window;
+pub use unused_textures::unused_textures_window;
@@ -0,0 +1,238 @@
+use egui::{CollapsingHeader, DragValue, Grid, RichText, ScrollArea, Ui, Window};
+
+use crate::{
+    ModelFolderState,
+    app::WARNING_COLOR,
+    editors::nutexb::format_name,
+    path::folder_display_name,
+    preferences::ModelBudgets,
+    statistics::{ModelFolderStatistics, format_size},
+};
+
+const MB: usize = 1024 * 1024;
+
+pub fn model_statistics_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    models: &[ModelFolderState],
+    budgets: &mut ModelBudgets,
+) -> bool {
+    let mut changed = false;
+
+    Window::new("Model Statistics")
+        .open(open)
+        .resizable(true)
+        .show(ctx, |ui| {
+            CollapsingHeader::new(RichText::new("Budgets").heading())
+                .default_open(false)
+                .show(ui, |ui| {
+                    changed |= edit_budgets(ui, budgets);
+                });
+            ui.separator();
+
+            ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    for (i, model) in models.iter().enumerate() {
+                        // Animation and texture folders don't have meaningful mesh statistics.
+                        if !model.is_model_folder() {
+                            continue;
+                        }
+
+                        let statistics = ModelFolderStatistics::from_model(&model.model);
+                        CollapsingHeader::new(folder_display_name(model))
+                            .id_salt(egui::Id::new("model_statistics").with(i))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                folder_statistics(ui, &statistics, budgets, i);
+                            });
+                    }
+                });
+        });
+
+    changed
+}
+
+fn edit_budgets(ui: &mut Ui, budgets: &mut ModelBudgets) -> bool {
+    let mut changed = false;
+
+    Grid::new("model_budgets").show(ui, |ui| {
+        let budget = &mut budgets.mesh_vertex_count;
+        changed |= edit_budget(ui, "Vertices Per Mesh", budget, 65535, 1);
+
+        let budget = &mut budgets.mesh_bone_count;
+        changed |= edit_budget(ui, "Bones Per Mesh", budget, 64, 1);
+
+        let budget = &mut budgets.folder_vertex_count;
+        changed |= edit_budget(ui, "Folder Vertices", budget, 100000, 1);
+
+        let budget = &mut budgets.texture_size;
+        changed |= edit_budget(ui, "Texture Size (MB)", budget, 8, MB);
+
+        let budget = &mut budgets.folder_texture_size;
+        changed |= edit_budget(ui, "Folder Texture Size (MB)", budget, 64, MB);
+    });
+
+    if ui.button("Reset Budgets").clicked() {
+        *budgets = ModelBudgets::default();
+        changed = true;
+    }
+
+    changed
+}
+
+fn edit_budget(
+    ui: &mut Ui,
+    label: &str,
+    budget: &mut Option<usize>,
+    default: usize,
+    scale: usize,
+) -> bool {
+    let mut changed = false;
+
+    let mut enabled = budget.is_some();
+    if ui.checkbox(&mut enabled, label).changed() {
+        *budget = enabled.then_some(default * scale);
+        changed = true;
+    }
+
+    // Edit the value in the displayed units to avoid large numbers for sizes.
+    if let Some(value) = budget {
+        let mut scaled = *value / scale;
+        if ui
+            .add(DragValue::new(&mut scaled).range(1..=usize::MAX))
+            .changed()
+        {
+            *value = scaled * scale;
+            changed = true;
+        }
+    } else {
+        ui.label("");
+    }
+    ui.end_row();
+
+    changed
+}
+
+fn folder_statistics(
+    ui: &mut Ui,
+    statistics: &ModelFolderStatistics,
+    budgets: &ModelBudgets,
+    i: usize,
+) {
+    let id = egui::Id::new("folder_statistics").with(i);
+
+    Grid::new(id.with("totals")).show(ui, |ui| {
+        ui.label("Mesh Objects");
+        ui.label(statistics.mesh_objects.len().to_string());
+        ui.end_row();
+
+        ui.label("Vertices");
+        budget_label(
+            ui,
+            statistics.vertex_count().to_string(),
+            statistics.vertex_count(),
+            budgets.folder_vertex_count,
+        );
+        ui.end_row();
+
+        ui.label("Vertex Indices");
+        ui.label(statistics.index_count().to_string());
+        ui.end_row();
+
+        ui.label("Vertex Attribute Size");
+        ui.label(format_size(statistics.attribute_size()));
+        ui.end_row();
+
+        ui.label("Skel Bones");
+        ui.label(statistics.skel_bone_count.to_string());
+        ui.end_row();
+
+        ui.label("Textures");
+        ui.label(statistics.textures.len().to_string());
+        ui.end_row();
+
+        ui.label("Texture Size");
+        budget_label(
+            ui,
+            format_size(statistics.texture_size()),
+            statistics.texture_size(),
+            budgets.folder_texture_size,
+        );
+        ui.end_row();
+    });
+
+    CollapsingHeader::new("Meshes")
+        .id_salt(id.with("meshes"))
+        .show(ui, |ui| {
+            Grid::new(id.with("mesh_grid"))
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.heading("Name");
+                    ui.heading("Subindex");
+                    ui.heading("Vertices");
+                    ui.heading("Indices");
+                    ui.heading("Attribute Size");
+                    ui.heading("Bones");
+                    ui.end_row();
+
+                    for o in &statistics.mesh_objects {
+                        ui.label(&o.name);
+                        ui.label(o.subindex.to_string());
+                        budget_label(
+                            ui,
+                            o.vertex_count.to_string(),
+                            o.vertex_count,
+                            budgets.mesh_vertex_count,
+                        );
+                        ui.label(o.index_count.to_string());
+                        ui.label(format_size(o.attribute_size));
+                        budget_label(
+                            ui,
+                            o.bone_count.to_string(),
+                            o.bone_count,
+                            budgets.mesh_bone_count,
+                        );
+                        ui.end_row();
+                    }
+                });
+        });
+
+    CollapsingHeader::new("Textures")
+        .id_salt(id.with("textures"))
+        .show(ui, |ui| {
+            Grid::new(id.with("texture_grid"))
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.heading("Name");
+                    ui.heading("Dimensions");
+                    ui.heading("Format");
+                    ui.heading("Mipmaps");
+                    ui.heading("Layers");
+                    ui.heading("Size");
+                    ui.end_row();
+
+                    for t in &statistics.textures {
+                        ui.label(&t.name);
+                        ui.label(format!("{}x{}x{}", t.width, t.height, t.depth));
+                        ui.label(format_name(t.format));
+                        ui.label(t.mipmap_count.to_string());
+                        ui.label(t.layer_count.to_string());
+                        budget_label(ui, format_size(t.size), t.size, budgets.texture_size);
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+fn budget_label(ui: &mut Ui, text: String, value: usize, budget: Option<usize>) {
+    match budget {
+        Some(budget) if value > budget => {
+            ui.label(RichText::new(text).color(WARNING_COLOR))
+                .on_hover_text("Exceeds the budget set in Model Statistics > Budgets.");
+        }
+        _ => {
+            ui.label(text);
+        }
+    }
+}
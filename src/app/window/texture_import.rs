@@ -0,0 +1,120 @@
+use egui::{ComboBox, Grid, RichText, Window};
+use log::error;
+
+use crate::{
+    ModelFolderState,
+    app::{TextureImportState, WARNING_COLOR},
+    editors::nutexb::format_name,
+    texture::{IMPORT_FORMATS, assigned_texture_param, default_import_format, import_nutexb},
+};
+
+/// Returns the index of the model folder if a nutexb was imported.
+pub fn texture_import_window(
+    ctx: &egui::Context,
+    state: &mut Option<TextureImportState>,
+    models: &mut [ModelFolderState],
+) -> Option<usize> {
+    let mut open = true;
+    let mut imported = false;
+
+    let import = state.as_mut()?;
+    let model = models.get_mut(import.folder_index)?;
+
+    Window::new("Import Image")
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            Grid::new("texture_import").show(ui, |ui| {
+                ui.label("Image");
+                ui.label(import.image_path.to_string_lossy());
+                ui.end_row();
+
+                ui.label("Name");
+                let name_changed = ui.text_edit_singleline(&mut import.name).changed();
+                ui.end_row();
+
+                // The usage depends on the name, so update formats that weren't selected manually.
+                let param_id = assigned_texture_param(&model.model.matls, &import.name);
+                if name_changed && !import.is_format_edited {
+                    import.format = default_import_format(param_id);
+                }
+
+                ui.label("Format");
+                ComboBox::from_id_salt("texture_import_format")
+                    .selected_text(format_name(import.format))
+                    .show_ui(ui, |ui| {
+                        for format in IMPORT_FORMATS {
+                            if ui
+                                .selectable_value(&mut import.format, format, format_name(format))
+                                .clicked()
+                            {
+                                import.is_format_edited = true;
+                            }
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Usage");
+                match param_id {
+                    Some(param_id) => ui.label(param_id.to_string()),
+                    None => ui.label("Not assigned to any materials"),
+                };
+                ui.end_row();
+            });
+
+            let file_name = format!("{}.nutexb", import.name);
+            let existing = model
+                .model
+                .nutexbs
+                .iter()
+                .position(|(f, _)| f.eq_ignore_ascii_case(&file_name));
+            if existing.is_some() {
+                ui.label(
+                    RichText::new(format!("{file_name} will be replaced.")).color(WARNING_COLOR),
+                );
+            }
+
+            ui.separator();
+            let text = if existing.is_some() {
+                "Replace"
+            } else {
+                "Import"
+            };
+            if ui
+                .add_enabled(!import.name.is_empty(), egui::Button::new(text))
+                .clicked()
+            {
+                match import_nutexb(&import.image_path, import.format, &import.name) {
+                    Ok(nutexb) => {
+                        // Only add textures that exist on disk to keep the file list accurate.
+                        let path = model.folder_path.join(&file_name);
+                        if let Err(e) = nutexb.write_to_file(&path) {
+                            error!("Failed to save nutexb to {path:?}: {e}");
+                            return;
+                        }
+
+                        match existing {
+                            Some(i) => {
+                                model.model.nutexbs[i] = (file_name, Some(nutexb));
+                                if let Some(changed) = model.changed.nutexbs.get_mut(i) {
+                                    *changed = false;
+                                }
+                            }
+                            None => {
+                                model.model.nutexbs.push((file_name, Some(nutexb)));
+                                model.changed.nutexbs.push(false);
+                            }
+                        }
+                        imported = true;
+                    }
+                    Err(e) => error!("Failed to import {:?}: {e}", import.image_path),
+                }
+            }
+        });
+
+    let folder_index = import.folder_index;
+    if !open || imported {
+        *state = None;
+    }
+    imported.then_some(folder_index)
+}
@@ -0,0 +1,125 @@
+use egui::{Grid, RichText, ScrollArea, Window};
+use log::error;
+
+use crate::{
+    ModelFolderState,
+    app::{UnusedTexturesState, WARNING_COLOR},
+    path::folder_display_name,
+    texture::unused_nutexbs,
+};
+
+/// Returns the index of the model folder if any nutexb files were deleted.
+pub fn unused_textures_window(
+    ctx: &egui::Context,
+    state: &mut Option<UnusedTexturesState>,
+    models: &mut [ModelFolderState],
+) -> Option<usize> {
+    let unused_textures = state.as_mut()?;
+    let index = unused_textures.folder_index;
+    let model = models.get_mut(index)?;
+
+    let mut open = true;
+    let mut confirmed = false;
+
+    Window::new(format!("Unused Textures ({})", folder_display_name(model)))
+        .open(&mut open)
+        .resizable(true)
+        .show(ctx, |ui| {
+            if !unused_textures.files_to_delete.is_empty() {
+                confirmed = confirm_delete(ui, &mut unused_textures.files_to_delete);
+                return;
+            }
+
+            let unused = unused_nutexbs(&model.model.nutexbs, &model.model.matls);
+            if unused.is_empty() {
+                ui.label("All textures are assigned to at least one material.");
+                return;
+            }
+
+            ui.label(
+                RichText::new("Deleting a texture permanently removes the file from disk.")
+                    .color(WARNING_COLOR),
+            );
+            ui.separator();
+
+            ScrollArea::vertical()
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    Grid::new("unused_textures").striped(true).show(ui, |ui| {
+                        for name in &unused {
+                            ui.label(name);
+                            if ui.button("Delete...").clicked() {
+                                unused_textures.files_to_delete = vec![name.clone()];
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+
+            ui.separator();
+            if ui
+                .button(format!("Delete All ({})...", unused.len()))
+                .clicked()
+            {
+                unused_textures.files_to_delete = unused;
+            }
+        });
+
+    let files_to_delete = if confirmed {
+        std::mem::take(&mut unused_textures.files_to_delete)
+    } else {
+        Vec::new()
+    };
+
+    if !open {
+        *state = None;
+    }
+
+    let mut deleted = false;
+    for name in files_to_delete {
+        let path = model.folder_path.join(&name);
+        if let Err(e) = std::fs::remove_file(&path) {
+            error!("Failed to delete {path:?}: {e}");
+            continue;
+        }
+
+        if let Some(i) = model.model.nutexbs.iter().position(|(f, _)| f == &name) {
+            model.model.nutexbs.remove(i);
+            if i < model.changed.nutexbs.len() {
+                model.changed.nutexbs.remove(i);
+            }
+        }
+        deleted = true;
+    }
+
+    deleted.then_some(index)
+}
+
+/// Returns `true` if the user confirmed deleting `files_to_delete`.
+fn confirm_delete(ui: &mut egui::Ui, files_to_delete: &mut Vec<String>) -> bool {
+    ui.label(RichText::new("Permanently delete the following files?").color(WARNING_COLOR));
+    ui.separator();
+
+    ScrollArea::vertical()
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            for name in files_to_delete.iter() {
+                ui.label(name);
+            }
+        });
+
+    ui.separator();
+    let mut confirmed = false;
+    ui.horizontal(|ui| {
+        if ui
+            .button(format!("Delete {} Files", files_to_delete.len()))
+            .clicked()
+        {
+            confirmed = true;
+        }
+        if ui.button("Cancel").clicked() {
+            files_to_delete.clear();
+        }
+    });
+    confirmed
+}
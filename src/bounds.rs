@@ -0,0 +1,139 @@
+use ssbh_data::{
+    Vector4,
+    mesh_data::{MeshObjectData, VectorData},
+    prelude::*,
+};
+
+/// An axis aligned bounding box with `min` and `max` corners.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BoundingBox {
+    pub min: [f32; 3],
+    pub max: [f32; 3],
+}
+
+/// A bounding sphere with a `center` and `radius`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BoundingSphere {
+    pub center: [f32; 3],
+    pub radius: f32,
+}
+
+impl BoundingSphere {
+    pub fn from_vector4(v: &Vector4) -> Self {
+        Self {
+            center: [v.x, v.y, v.z],
+            radius: v.w,
+        }
+    }
+
+    pub fn to_vector4(self) -> Vector4 {
+        Vector4::new(self.center[0], self.center[1], self.center[2], self.radius)
+    }
+
+    pub fn contains_points(&self, points: &[[f32; 3]]) -> bool {
+        // Use a small tolerance since bounds are stored with limited precision.
+        let radius = self.radius + 0.001 * self.radius.max(1.0);
+        points.iter().all(|p| distance(&self.center, p) <= radius)
+    }
+}
+
+/// The vertex positions for the first position attribute of `o`.
+pub fn mesh_object_points(o: &MeshObjectData) -> Vec<[f32; 3]> {
+    match o.positions.first().map(|a| &a.data) {
+        Some(VectorData::Vector2(values)) => values.iter().map(|v| [v[0], v[1], 0.0]).collect(),
+        Some(VectorData::Vector3(values)) => values.clone(),
+        Some(VectorData::Vector4(values)) => values.iter().map(|v| [v[0], v[1], v[2]]).collect(),
+        None => Vec::new(),
+    }
+}
+
+pub fn bounding_box(points: &[[f32; 3]]) -> Option<BoundingBox> {
+    let first = points.first()?;
+    Some(points.iter().fold(
+        BoundingBox {
+            min: *first,
+            max: *first,
+        },
+        |b, p| BoundingBox {
+            min: [b.min[0].min(p[0]), b.min[1].min(p[1]), b.min[2].min(p[2])],
+            max: [b.max[0].max(p[0]), b.max[1].max(p[1]), b.max[2].max(p[2])],
+        },
+    ))
+}
+
+pub fn bounding_sphere(points: &[[f32; 3]]) -> Option<BoundingSphere> {
+    // Center the sphere on the bounding box to avoid outliers shifting the center.
+    // This isn't the smallest possible sphere but is fast and always contains the points.
+    let b = bounding_box(points)?;
+    let center = [
+        (b.min[0] + b.max[0]) / 2.0,
+        (b.min[1] + b.max[1]) / 2.0,
+        (b.min[2] + b.max[2]) / 2.0,
+    ];
+    let radius = points
+        .iter()
+        .map(|p| distance(&center, p))
+        .fold(0.0f32, f32::max);
+    Some(BoundingSphere { center, radius })
+}
+
+/// Recalculate the bounding sphere for each group in `meshex` from the mesh objects in `mesh`.
+/// Groups without any matching mesh objects are not changed.
+pub fn recompute_meshex_bounds(meshex: &mut MeshExData, mesh: &MeshData) {
+    for group in &mut meshex.mesh_object_groups {
+        let points = group_points(&group.mesh_object_full_name, mesh);
+        if let Some(sphere) = bounding_sphere(&points) {
+            group.bounding_sphere = sphere.to_vector4();
+        }
+    }
+}
+
+/// The vertex positions for all mesh objects with the given `full_name`.
+pub fn group_points(full_name: &str, mesh: &MeshData) -> Vec<[f32; 3]> {
+    mesh.objects
+        .iter()
+        .filter(|o| o.name == full_name)
+        .flat_map(mesh_object_points)
+        .collect()
+}
+
+fn distance(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounding_box_empty() {
+        assert_eq!(None, bounding_box(&[]));
+    }
+
+    #[test]
+    fn bounding_box_sphere_points() {
+        let points = [[-1.0, 0.0, 2.0], [1.0, 4.0, 2.0], [0.0, 2.0, 2.0]];
+        assert_eq!(
+            Some(BoundingBox {
+                min: [-1.0, 0.0, 2.0],
+                max: [1.0, 4.0, 2.0]
+            }),
+            bounding_box(&points)
+        );
+
+        let sphere = bounding_sphere(&points).unwrap();
+        assert_eq!([0.0, 2.0, 2.0], sphere.center);
+        assert_eq!(5.0f32.sqrt(), sphere.radius);
+        assert!(sphere.contains_points(&points));
+    }
+
+    #[test]
+    fn sphere_missing_points() {
+        let sphere = BoundingSphere {
+            center: [0.0; 3],
+            radius: 1.0,
+        };
+        assert!(sphere.contains_points(&[[0.0, 1.0, 0.0]]));
+        assert!(!sphere.contains_points(&[[0.0, 2.0, 0.0]]));
+    }
+}
@@ -3,13 +3,14 @@ use crate::{
     app::{AnimEditorState, HlpbEditorState, MeshEditorState, ModlEditorState, SkelEditorState},
     editors::{
         adj::adj_editor, anim::anim_editor, hlpb::hlpb_editor, mesh::mesh_editor,
-        meshex::meshex_editor, modl::modl_editor, skel::skel_editor,
+        meshex::meshex_editor, modl::modl_editor, skel::skel_editor, xmb::xmb_editor,
     },
     model_folder::{FileChanged, ModelFolderState},
 };
 use egui::Context;
 use ssbh_data::prelude::*;
 use ssbh_wgpu::ModelFiles;
+use xmb_lib::XmbFile;
 
 pub mod adj;
 pub mod anim;
@@ -20,6 +21,8 @@ pub mod meshex;
 pub mod modl;
 pub mod nutexb;
 pub mod skel;
+pub mod swing;
+pub mod xmb;
 
 /// The logic required to open and close an editor window from an open file index.
 pub trait Editor {
@@ -157,6 +160,12 @@ impl Editor for MeshExData {
             name,
             meshex,
             find_file(&model.model.meshes, "model.numshb"),
+            model
+                .validation
+                .meshex_errors
+                .get(open_file_index.as_ref()?)
+                .map(|e| e.as_slice())
+                .unwrap_or_default(),
         ))
     }
 
@@ -165,6 +174,38 @@ impl Editor for MeshExData {
     }
 }
 
+impl Editor for XmbFile {
+    type EditorState = ();
+
+    fn editor(
+        ctx: &Context,
+        model: &mut ModelFolderState,
+        open_file_index: &mut Option<usize>,
+        _: &mut Self::EditorState,
+        _: bool,
+    ) -> Option<EditorResponse> {
+        let (name, xmb) = get_file_to_edit(&mut model.model.xmbs, *open_file_index)?;
+        let numdlb_names: Vec<_> = model.model.modls.iter().map(|(f, _)| f.as_str()).collect();
+        Some(xmb_editor(
+            ctx,
+            &model.folder_path,
+            name,
+            xmb,
+            &numdlb_names,
+            model
+                .validation
+                .xmb_errors
+                .get(open_file_index.as_ref()?)
+                .map(|e| e.as_slice())
+                .unwrap_or_default(),
+        ))
+    }
+
+    fn set_changed(response: &EditorResponse, changed: &mut FileChanged, index: usize) {
+        response.set_changed(&mut changed.xmbs[index])
+    }
+}
+
 impl Editor for MeshData {
     type EditorState = MeshEditorState;
 
@@ -240,7 +281,7 @@ fn get_file_to_edit<T>(
         .and_then(|(name, file)| Some((name, file.as_mut()?)))
 }
 
-fn find_file<'a, T>(files: &'a [(String, FileResult<T>)], name: &str) -> Option<&'a T> {
+pub fn find_file<'a, T>(files: &'a [(String, FileResult<T>)], name: &str) -> Option<&'a T> {
     files
         .iter()
         .find(|(f, _)| f == name)
@@ -1,17 +1,21 @@
 use crate::{
     EditorResponse,
     app::{
-        MatlEditorState, PresetMode, ShaderFinderState, UiState, display_validation_errors,
+        ERROR_COLOR, MatlCompareSource, MatlEditorState, PresetMode, ShaderFinderState, UiState,
+        WARNING_COLOR, display_validation_errors,
         icon::{draggable_icon, warning_icon},
         warning_icon_text,
     },
     horizontal_separator_empty,
     material::*,
-    path::{folder_editor_title, presets_file},
-    presets::{load_json_presets, load_xml_presets},
+    path::{folder_editor_title, preset_library_file},
+    presets::{
+        MaterialPreset, PresetCategory, export_json_presets, export_xml_presets, load_json_presets,
+        load_xml_presets, save_preset_library,
+    },
     save_file, save_file_as,
     thumbnail::{TextureDimension, Thumbnail},
-    validation::{MatlValidationError, MatlValidationErrorKind},
+    validation::{MatlValidationError, MatlValidationErrorKind, mesh_attribute_names},
     widgets::*,
 };
 use egui::{
@@ -22,9 +26,15 @@ use egui::{
 use egui_dnd::dnd;
 use log::error;
 use rfd::FileDialog;
-use ssbh_data::{Color4f, Vector4, matl_data::*, modl_data::ModlEntryData, prelude::*};
+use ssbh_data::{
+    Color4f, Vector4, anim_data::AnimData, matl_data::*, modl_data::ModlEntryData, prelude::*,
+};
 use ssbh_wgpu::{ShaderDatabase, ShaderProgram, split_param};
-use std::{path::Path, str::FromStr};
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+    str::FromStr,
+};
 use strum::IntoEnumIterator;
 
 const UNUSED_PARAM: &str =
@@ -41,7 +51,12 @@ const LIGHTSET_DESCRIPTION: &str = "Uses directional lighting from the lighting
 const ANISOTROPIC_ROTATION_DESCRIPTION: &str =
     "Use the PRM alpha to rotate the anisotropic highlight.";
 const NO_USER_PRESETS: &str = "No user material presets detected. \
-Make sure the presets.json file is present and contains valid JSON materials.";
+Add presets with Material > Add Material to Presets or import presets in the Material Preset Editor.";
+const NO_MODL_USAGE: &str =
+    "Material usage requires a model.numdlb file to determine material assignments.";
+const UNUSED_MATERIAL: &str = "This material is not assigned to any mesh objects in the model.numdlb \
+and will not be used in game. Remove unused materials with Material > Remove Unused Materials.";
+const NO_MATCHING_PRESETS: &str = "No presets match the current search and category.";
 const SHADER_ATTRIBUTES_DESCRIPTION: &str =
     "The mesh attributes required by the shader. The XYZW suffixes indicate accessed components.";
 const SHADER_COMPLEXITY_DESCRIPTION: &str = "The estimated complexity of this shader relative to other shaders. \
@@ -55,12 +70,18 @@ pub fn matl_editor(
     state: &mut MatlEditorState,
     matl: &mut MatlData,
     modl: Option<&mut ModlData>,
+    mesh: Option<&MeshData>,
+    anims: &[(String, Option<AnimData>)],
     validation_errors: &[MatlValidationError],
     folder_thumbnails: &[Thumbnail],
     default_thumbnails: &[Thumbnail],
     shader_database: &ShaderDatabase,
-    material_presets: &mut Vec<MatlEntryData>,
-    default_presets: &[MatlEntryData],
+    material_presets: &mut Vec<MaterialPreset>,
+    default_presets: &[MaterialPreset],
+    other_matls: &[(String, &MatlData)],
+    clipboard: &mut Option<MatlEntryData>,
+    texture_suffix_rules: &mut Vec<TextureSuffixRule>,
+    material_variant_rules: &[MaterialVariantRule],
     red_checkerboard: egui::TextureId,
     yellow_checkerboard: egui::TextureId,
     dark_mode: bool,
@@ -70,7 +91,7 @@ pub fn matl_editor(
     let mut saved = false;
 
     let title = folder_editor_title(folder_name, file_name);
-    Window::new(format!("Matl Editor ({title})"))
+    let window_response = Window::new(format!("Matl Editor ({title})"))
         .open(&mut open)
         .default_size(egui::Vec2::new(700.0, 900.0))
         .resizable(true)
@@ -81,7 +102,8 @@ pub fn matl_editor(
                     matl,
                     &modl,
                     state,
-                    material_presets,
+                    clipboard,
+                    material_variant_rules,
                     folder_name,
                     file_name,
                 );
@@ -101,6 +123,7 @@ pub fn matl_editor(
                                 dark_mode,
                                 validation_errors,
                                 state,
+                                clipboard,
                             );
                         });
                 });
@@ -121,18 +144,70 @@ pub fn matl_editor(
                 }
                 changed |= preset_changed;
 
+                new_preset_window(state, ctx, material_presets);
+
                 let entry = matl.entries.get_mut(state.selected_material_index);
                 let (open, shader_finder_changed) =
-                    shader_finder_window(state, ctx, entry, shader_database);
+                    shader_finder_window(state, ctx, entry, mesh, shader_database);
                 if !open {
                     state.shader_finder_window_open = false;
                 }
                 changed |= shader_finder_changed;
 
+                let (open, compare_changed) = compare_window(
+                    state,
+                    ctx,
+                    &mut matl.entries,
+                    other_matls,
+                    material_presets,
+                    default_presets,
+                );
+                if !open {
+                    state.compare_window_open = false;
+                }
+                changed |= compare_changed;
+
+                let (open, auto_assign_changed) = auto_assign_textures_window(
+                    state,
+                    ctx,
+                    &mut matl.entries,
+                    folder_thumbnails,
+                    default_thumbnails,
+                    texture_suffix_rules,
+                );
+                if !open {
+                    state.auto_assign_window_open = false;
+                }
+                changed |= auto_assign_changed;
+
+                material_usage_window(
+                    state,
+                    ctx,
+                    &matl.entries,
+                    modl.as_deref(),
+                    anims,
+                    material_variant_rules,
+                );
+
                 ScrollArea::vertical()
                     .auto_shrink([false; 2])
                     .show(ui, |ui| {
-                        if let Some(entry) = matl.entries.get_mut(state.selected_material_index) {
+                        if let Some(indices) = batch_selection(state, matl.entries.len()) {
+                            let mut entries: Vec<_> = matl
+                                .entries
+                                .iter_mut()
+                                .enumerate()
+                                .filter(|(i, _)| indices.contains(i))
+                                .map(|(_, e)| e)
+                                .collect();
+                            changed |= edit_matl_entries_batch(
+                                ui,
+                                &mut entries,
+                                &mut state.batch_add_missing,
+                            );
+                        } else if let Some(entry) =
+                            matl.entries.get_mut(state.selected_material_index)
+                        {
                             changed |= edit_matl_entry(
                                 ctx,
                                 ui,
@@ -151,6 +226,33 @@ pub fn matl_editor(
             });
         });
 
+    if let Some((old_label, new_label)) = state.renamed_material_label.take() {
+        changed |= rename_material_variants(
+            &mut matl.entries,
+            &old_label,
+            &new_label,
+            material_variant_rules,
+        );
+    }
+
+    // Support pasting material JSON copied from text editors or other applications.
+    // Only update the clipboard, so materials are changed using the explicit paste actions.
+    // Text edits handle their own paste events.
+    if window_response.is_some_and(|r| r.response.contains_pointer())
+        && ctx.memory(|m| m.focused().is_none())
+        && let Some(text) = ctx.input(|i| {
+            i.events.iter().find_map(|e| match e {
+                egui::Event::Paste(text) => Some(text.clone()),
+                _ => None,
+            })
+        })
+    {
+        // Ignore text that isn't material JSON since pasting arbitrary text is expected.
+        if let Ok(source) = serde_json::from_str::<MatlEntryData>(&text) {
+            *clipboard = Some(source);
+        }
+    }
+
     EditorResponse {
         open,
         changed,
@@ -159,12 +261,22 @@ pub fn matl_editor(
     }
 }
 
+fn copy_material(ctx: &Context, clipboard: &mut Option<MatlEntryData>, entry: MatlEntryData) {
+    // Also copy to the system clipboard to allow pasting into text editors.
+    match serde_json::to_string_pretty(&entry) {
+        Ok(text) => ctx.copy_text(text),
+        Err(e) => error!("Failed to convert material to JSON: {e}"),
+    }
+    *clipboard = Some(entry);
+}
+
 fn select_material_dnd(
     entries: &mut Vec<MatlEntryData>,
     ui: &mut Ui,
     dark_mode: bool,
     validation_errors: &[MatlValidationError],
     state: &mut MatlEditorState,
+    clipboard: &mut Option<MatlEntryData>,
 ) -> bool {
     let mut changed = false;
 
@@ -172,6 +284,9 @@ fn select_material_dnd(
     let mut item_indices: Vec<_> = (0..entries.len()).collect();
 
     let mut index_to_delete = None;
+    let mut index_to_paste = None;
+
+    let batch_indices = batch_selection(state, entries.len()).unwrap_or_default();
 
     let response = dnd(ui, "matl_dnd").show_vec(&mut item_indices, |ui, item_index, handle, _| {
         ui.horizontal(|ui| {
@@ -195,10 +310,14 @@ fn select_material_dnd(
             // Wrap long material labels.
             ui.style_mut().wrap_mode = Some(TextWrapMode::Wrap);
 
-            let mut response =
-                ui.selectable_label(state.selected_material_index == *item_index, text);
+            let selected =
+                state.selected_material_index == *item_index || batch_indices.contains(item_index);
+            let mut response = ui
+                .selectable_label(selected, text)
+                .on_hover_text("Ctrl or Shift click to select multiple materials.");
             if response.clicked() {
-                state.selected_material_index = *item_index;
+                let modifiers = ui.input(|i| i.modifiers);
+                select_material(state, *item_index, modifiers);
             }
 
             if !errors.is_empty() {
@@ -213,6 +332,18 @@ fn select_material_dnd(
             }
 
             response.context_menu(|ui| {
+                if ui.button("Copy").clicked() {
+                    copy_material(ui.ctx(), clipboard, entry.clone());
+                }
+
+                if ui
+                    .add_enabled(clipboard.is_some(), Button::new("Paste Parameters"))
+                    .clicked()
+                {
+                    index_to_paste = Some(*item_index);
+                }
+                ui.separator();
+
                 // TODO: Also add a menu option?
                 if ui.button("Delete").clicked() {
                     index_to_delete = Some(*item_index);
@@ -227,22 +358,90 @@ fn select_material_dnd(
             .iter()
             .position(|i| *i == state.selected_material_index)
             .unwrap_or_default();
+        state.selected_material_indices = state
+            .selected_material_indices
+            .iter()
+            .filter_map(|i| item_indices.iter().position(|j| j == i))
+            .collect();
+        changed = true;
+    }
+
+    if let Some(i) = index_to_paste
+        && let Some(entry) = entries.get_mut(i)
+        && let Some(source) = clipboard
+    {
+        paste_parameters(entry, source);
         changed = true;
     }
 
     if let Some(i) = index_to_delete {
         entries.remove(i);
+        state.selected_material_indices = state
+            .selected_material_indices
+            .iter()
+            .filter(|j| **j != i)
+            .map(|j| if *j > i { j - 1 } else { *j })
+            .collect();
         changed = true;
     }
 
     changed
 }
 
+fn select_material(state: &mut MatlEditorState, index: usize, modifiers: egui::Modifiers) {
+    // The multiple selection may be outdated if another action changed the selected material.
+    if !state
+        .selected_material_indices
+        .contains(&state.selected_material_index)
+    {
+        state.selected_material_indices = BTreeSet::from([state.selected_material_index]);
+    }
+
+    if modifiers.command {
+        if state.selected_material_indices.insert(index) {
+            state.selected_material_index = index;
+        } else if state.selected_material_indices.len() > 1 {
+            state.selected_material_indices.remove(&index);
+            if state.selected_material_index == index
+                && let Some(first) = state.selected_material_indices.first()
+            {
+                state.selected_material_index = *first;
+            }
+        }
+    } else if modifiers.shift {
+        // Select the range between the previously selected material and this one.
+        let start = state.selected_material_index.min(index);
+        let end = state.selected_material_index.max(index);
+        state.selected_material_indices = (start..=end).collect();
+    } else {
+        state.selected_material_index = index;
+        state.selected_material_indices = BTreeSet::from([index]);
+    }
+}
+
+/// The selected material indices if more than one material is selected.
+fn batch_selection(state: &MatlEditorState, entry_count: usize) -> Option<BTreeSet<usize>> {
+    if !state
+        .selected_material_indices
+        .contains(&state.selected_material_index)
+    {
+        return None;
+    }
+
+    let indices: BTreeSet<_> = state
+        .selected_material_indices
+        .iter()
+        .copied()
+        .filter(|i| *i < entry_count)
+        .collect();
+    (indices.len() > 1).then_some(indices)
+}
+
 // TODO: Validate presets?
 pub fn preset_editor(
     ctx: &egui::Context,
     ui_state: &mut UiState,
-    user_presets: &mut Vec<MatlEntryData>,
+    user_presets: &mut Vec<MaterialPreset>,
     default_thumbnails: &[Thumbnail],
     shader_database: &ShaderDatabase,
     red_checkerboard: egui::TextureId,
@@ -263,12 +462,12 @@ pub fn preset_editor(
                     ScrollArea::vertical()
                         .auto_shrink([false; 2])
                         .show(ui, |ui| {
-                            select_material_dnd(
+                            select_preset_dnd(
                                 user_presets,
                                 ui,
                                 dark_mode,
-                                &[],
                                 &mut ui_state.preset_editor,
+                                &mut ui_state.material_clipboard,
                             );
                         });
                 });
@@ -277,15 +476,18 @@ pub fn preset_editor(
                 ScrollArea::vertical()
                     .auto_shrink([false; 2])
                     .show(ui, |ui| {
-                        // Use an empty model thumbnail list to encourage using default textures.
-                        // These textures will be replaced by param specific defaults anyway.
-                        if let Some(entry) =
+                        if let Some(preset) =
                             user_presets.get_mut(ui_state.preset_editor.selected_material_index)
                         {
+                            edit_preset_info(ui, preset, &mut ui_state.preset_editor);
+                            horizontal_separator_empty(ui);
+
+                            // Use an empty model thumbnail list to encourage using default textures.
+                            // These textures will be replaced by param specific defaults anyway.
                             edit_matl_entry(
                                 ctx,
                                 ui,
-                                entry,
+                                &mut preset.material,
                                 None,
                                 &[],
                                 &[],
@@ -301,12 +503,174 @@ pub fn preset_editor(
         });
 }
 
-fn presets_menu(ui: &mut Ui, user_presets: &mut Vec<MatlEntryData>) {
+fn select_preset_dnd(
+    presets: &mut Vec<MaterialPreset>,
+    ui: &mut Ui,
+    dark_mode: bool,
+    state: &mut MatlEditorState,
+    clipboard: &mut Option<MatlEntryData>,
+) {
+    // TODO: Avoid allocating here.
+    let mut item_indices: Vec<_> = (0..presets.len()).collect();
+
+    let mut index_to_delete = None;
+    let mut index_to_paste = None;
+
+    let response =
+        dnd(ui, "preset_dnd").show_vec(&mut item_indices, |ui, item_index, handle, _| {
+            ui.horizontal(|ui| {
+                let preset = &presets[*item_index];
+
+                handle.ui(ui, |ui| {
+                    draggable_icon(ui, dark_mode);
+                });
+
+                // Wrap long preset names.
+                ui.style_mut().wrap_mode = Some(TextWrapMode::Wrap);
+
+                ui.selectable_value(
+                    &mut state.selected_material_index,
+                    *item_index,
+                    &preset.name,
+                )
+                .on_hover_text(format!(
+                    "{} ({})",
+                    preset.category, preset.material.shader_label
+                ))
+                .context_menu(|ui| {
+                    if ui.button("Copy").clicked() {
+                        copy_material(ui.ctx(), clipboard, preset.material.clone());
+                    }
+
+                    if ui
+                        .add_enabled(clipboard.is_some(), Button::new("Paste Parameters"))
+                        .clicked()
+                    {
+                        index_to_paste = Some(*item_index);
+                    }
+                    ui.separator();
+
+                    if ui.button("Delete").clicked() {
+                        index_to_delete = Some(*item_index);
+                    }
+                });
+            });
+        });
+
+    if let Some(response) = response.final_update() {
+        egui_dnd::utils::shift_vec(response.from, response.to, presets);
+        state.selected_material_index = item_indices
+            .iter()
+            .position(|i| *i == state.selected_material_index)
+            .unwrap_or_default();
+    }
+
+    if let Some(i) = index_to_paste
+        && let Some(preset) = presets.get_mut(i)
+        && let Some(source) = clipboard
+    {
+        paste_parameters(&mut preset.material, source);
+    }
+
+    if let Some(i) = index_to_delete {
+        presets.remove(i);
+        if state.selected_material_index > i {
+            state.selected_material_index -= 1;
+        }
+    }
+}
+
+fn edit_preset_info(ui: &mut Ui, preset: &mut MaterialPreset, state: &mut MatlEditorState) {
+    Grid::new("preset_info").num_columns(2).show(ui, |ui| {
+        ui.label("Name");
+        ui.add(TextEdit::singleline(&mut preset.name).desired_width(400.0));
+        ui.end_row();
+
+        ui.label("Description");
+        ui.add(
+            TextEdit::multiline(&mut preset.description)
+                .desired_rows(2)
+                .desired_width(400.0),
+        );
+        ui.end_row();
+
+        ui.label("Category");
+        preset_category_combo(ui, "preset_category", &mut preset.category);
+        ui.end_row();
+
+        ui.label("Tags");
+        ui.vertical(|ui| {
+            edit_preset_tags(ui, &mut preset.tags, &mut state.new_preset_tag);
+        });
+        ui.end_row();
+
+        ui.label("Thumbnail");
+        ui.horizontal(|ui| {
+            ui.label(preset.thumbnail.as_deref().unwrap_or("None"));
+            if ui.button("Browse...").clicked()
+                && let Some(file) = FileDialog::new()
+                    .add_filter("Image", &["png", "jpg", "jpeg"])
+                    .pick_file()
+            {
+                preset.thumbnail = Some(file.to_string_lossy().to_string());
+            }
+            if ui
+                .add_enabled(preset.thumbnail.is_some(), Button::new("Clear"))
+                .clicked()
+            {
+                preset.thumbnail = None;
+            }
+        });
+        ui.end_row();
+    });
+}
+
+fn edit_preset_tags(ui: &mut Ui, tags: &mut Vec<String>, new_tag: &mut String) {
+    let mut tag_to_remove = None;
+    ui.horizontal_wrapped(|ui| {
+        for (i, tag) in tags.iter().enumerate() {
+            if ui
+                .button(format!("{tag} ❌"))
+                .on_hover_text("Remove tag")
+                .clicked()
+            {
+                tag_to_remove = Some(i);
+            }
+        }
+    });
+    if let Some(i) = tag_to_remove {
+        tags.remove(i);
+    }
+
+    ui.horizontal(|ui| {
+        let response = ui.add(TextEdit::singleline(new_tag).hint_text("New tag"));
+        let entered = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if (ui.button("Add").clicked() || entered) && !new_tag.trim().is_empty() {
+            let tag = new_tag.trim().to_string();
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+            new_tag.clear();
+        }
+    });
+}
+
+fn preset_category_combo(ui: &mut Ui, id: &str, category: &mut PresetCategory) {
+    ComboBox::from_id_salt(id)
+        .width(200.0)
+        .selected_text(category.to_string())
+        .show_ui(ui, |ui| {
+            for c in PresetCategory::iter() {
+                ui.selectable_value(category, c, c.to_string());
+            }
+        });
+}
+
+fn presets_menu(ui: &mut Ui, user_presets: &mut Vec<MaterialPreset>) {
     egui::MenuBar::new().ui(ui, |ui| {
         ui.menu_button("File", |ui| {
             if ui.button("Save").clicked() {
-                let path = presets_file();
-                save_material_presets(user_presets, path);
+                save_preset_library(user_presets, preset_library_file());
             }
         });
 
@@ -333,10 +697,32 @@ fn presets_menu(ui: &mut Ui, user_presets: &mut Vec<MatlEntryData>) {
             }
         });
 
+        ui.menu_button("Export", |ui| {
+            // Export presets for use in other tools.
+            if ui
+                .add(Button::new("JSON Presets (ssbh_data_json)").wrap_mode(TextWrapMode::Extend))
+                .clicked()
+                && let Some(file) = FileDialog::new()
+                    .add_filter("Matl JSON", &["json"])
+                    .save_file()
+            {
+                save_presets_to_file(user_presets, file, export_json_presets);
+            }
+
+            if ui
+                .add(Button::new("XML Presets (Cross Mod)").wrap_mode(TextWrapMode::Extend))
+                .clicked()
+                && let Some(file) = FileDialog::new()
+                    .add_filter("Matl XML", &["xml"])
+                    .save_file()
+            {
+                save_presets_to_file(user_presets, file, export_xml_presets);
+            }
+        });
+
         ui.menu_button("Material", |ui| {
             if ui.button("Add New Material").clicked() {
-                let new_entry = default_material();
-                user_presets.push(new_entry);
+                user_presets.push(MaterialPreset::new(default_material()));
             }
 
             if ui.button("Remove Duplicates").clicked() {
@@ -347,7 +733,7 @@ fn presets_menu(ui: &mut Ui, user_presets: &mut Vec<MatlEntryData>) {
     });
 }
 
-fn remove_duplicates(entries: &mut Vec<MatlEntryData>) {
+fn remove_duplicates<T: PartialEq + Clone>(entries: &mut Vec<T>) {
     // Remove duplicates using PartialEq while preserving ordering.
     // TODO: Avoid clone?
     let mut visited = Vec::with_capacity(entries.len());
@@ -361,101 +747,525 @@ fn remove_duplicates(entries: &mut Vec<MatlEntryData>) {
     });
 }
 
-fn remove_unused_materials(matl_entries: &mut Vec<MatlEntryData>, modl_entries: &[ModlEntryData]) {
-    let mut visited = Vec::new();
+fn remove_unused_materials(
+    matl_entries: &mut Vec<MatlEntryData>,
+    modl_entries: &[ModlEntryData],
+    rules: &[MaterialVariantRule],
+) {
+    let used_labels = used_material_labels(modl_entries, rules);
+    matl_entries.retain(|item| used_labels.contains(&item.material_label));
+}
 
-    for modl_entry in modl_entries.iter() {
-        visited.push(modl_entry.material_label.clone());
+fn load_presets_from_file<F: Fn(&[u8]) -> anyhow::Result<Vec<MatlEntryData>>>(
+    presets: &mut Vec<MaterialPreset>,
+    file: std::path::PathBuf,
+    load_presets: F,
+) {
+    match std::fs::read(&file)
+        .map_err(|e| {
+            error!("Error reading presets file {file:?}: {e}");
+            e.into()
+        })
+        .and_then(|bytes| load_presets(&bytes))
+    {
+        Ok(new_presets) => presets.extend(new_presets.into_iter().map(MaterialPreset::new)),
+        Err(e) => error!("Error importing presets: {e}"),
     }
+}
 
-    if visited.contains(&String::from("EyeL")) {
-        //World of Light purple
-        visited.push(String::from("EyeLD"));
-        //Final Smash
-        visited.push(String::from("EyeLG"));
-        //World of Light red
-        visited.push(String::from("EyeLL"));
+fn save_presets_to_file<F: Fn(&[MatlEntryData]) -> anyhow::Result<String>>(
+    presets: &[MaterialPreset],
+    file: std::path::PathBuf,
+    export_presets: F,
+) {
+    let entries: Vec<_> = presets.iter().map(|p| p.material.clone()).collect();
+    match export_presets(&entries) {
+        Ok(text) => {
+            if let Err(e) = std::fs::write(&file, text) {
+                error!("Error writing presets file {file:?}: {e}");
+            }
+        }
+        Err(e) => error!("Error exporting presets: {e}"),
     }
+}
 
-    if visited.contains(&String::from("EyeR")) {
-        //World of Light purple
-        visited.push(String::from("EyeRD"));
-        //Final Smash
-        visited.push(String::from("EyeRG"));
-        //World of Light red
-        visited.push(String::from("EyeRL"));
-    }
+fn compare_window(
+    state: &mut MatlEditorState,
+    ctx: &egui::Context,
+    entries: &mut [MatlEntryData],
+    other_matls: &[(String, &MatlData)],
+    material_presets: &[MaterialPreset],
+    default_presets: &[MaterialPreset],
+) -> (bool, bool) {
+    let mut open = state.compare_window_open;
+    let mut changed = false;
+    Window::new("Compare Materials")
+        .open(&mut open)
+        .resizable(true)
+        .show(ctx, |ui| {
+            let source_name = |source: MatlCompareSource| match source {
+                MatlCompareSource::CurrentFile => "Current File".to_owned(),
+                MatlCompareSource::OtherFile(i) => other_matls
+                    .get(i)
+                    .map(|(name, _)| name.clone())
+                    .unwrap_or_default(),
+                MatlCompareSource::Preset(PresetMode::Default) => "Default Presets".to_owned(),
+                MatlCompareSource::Preset(PresetMode::User) => "User Presets".to_owned(),
+            };
 
-    if visited.contains(&String::from("EyeL1")) {
-        //World of Light purple
-        visited.push(String::from("EyeLD1"));
-        //Final Smash
-        visited.push(String::from("EyeLG1"));
-        //World of Light red
-        visited.push(String::from("EyeLL1"));
-    }
+            let sources = [
+                MatlCompareSource::CurrentFile,
+                MatlCompareSource::Preset(PresetMode::Default),
+                MatlCompareSource::Preset(PresetMode::User),
+            ]
+            .into_iter()
+            .chain((0..other_matls.len()).map(MatlCompareSource::OtherFile));
+
+            let compare = &mut state.compare;
+            Grid::new("compare_source").num_columns(2).show(ui, |ui| {
+                ui.label("Source");
+                ComboBox::from_id_salt("compare_source")
+                    .width(300.0)
+                    .selected_text(source_name(compare.source))
+                    .show_ui(ui, |ui| {
+                        for source in sources {
+                            if ui
+                                .selectable_value(&mut compare.source, source, source_name(source))
+                                .changed()
+                            {
+                                compare.entry_index = 0;
+                            }
+                        }
+                    });
+                ui.end_row();
 
-    if visited.contains(&String::from("EyeR1")) {
-        //World of Light purple
-        visited.push(String::from("EyeRD1"));
-        //Final Smash
-        visited.push(String::from("EyeRG1"));
-        //World of Light red
-        visited.push(String::from("EyeRL1"));
-    }
+                let source_entries: Vec<&MatlEntryData> = match compare.source {
+                    MatlCompareSource::CurrentFile => entries.iter().collect(),
+                    MatlCompareSource::OtherFile(i) => other_matls
+                        .get(i)
+                        .map(|(_, matl)| matl.entries.iter().collect())
+                        .unwrap_or_default(),
+                    MatlCompareSource::Preset(PresetMode::Default) => {
+                        default_presets.iter().map(|p| &p.material).collect()
+                    }
+                    MatlCompareSource::Preset(PresetMode::User) => {
+                        material_presets.iter().map(|p| &p.material).collect()
+                    }
+                };
+
+                ui.label("Material");
+                ComboBox::from_id_salt("compare_material")
+                    .width(300.0)
+                    .selected_text(
+                        source_entries
+                            .get(compare.entry_index)
+                            .map(|e| e.material_label.as_str())
+                            .unwrap_or_default(),
+                    )
+                    .show_ui(ui, |ui| {
+                        for (i, entry) in source_entries.iter().enumerate() {
+                            ui.selectable_value(&mut compare.entry_index, i, &entry.material_label);
+                        }
+                    });
+                ui.end_row();
+            });
+            ui.checkbox(&mut compare.differences_only, "Show Differences Only");
+            horizontal_separator_empty(ui);
+
+            // Clone the other material to allow comparing with materials in the same file.
+            let other = match compare.source {
+                MatlCompareSource::CurrentFile => entries.get(compare.entry_index),
+                MatlCompareSource::OtherFile(i) => other_matls
+                    .get(i)
+                    .and_then(|(_, matl)| matl.entries.get(compare.entry_index)),
+                MatlCompareSource::Preset(PresetMode::Default) => default_presets
+                    .get(compare.entry_index)
+                    .map(|p| &p.material),
+                MatlCompareSource::Preset(PresetMode::User) => material_presets
+                    .get(compare.entry_index)
+                    .map(|p| &p.material),
+            }
+            .cloned();
 
-    if visited.contains(&String::from("EyeL2")) {
-        //World of Light purple
-        visited.push(String::from("EyeLD2"));
-        //Final Smash
-        visited.push(String::from("EyeLG2"));
-        //World of Light red
-        visited.push(String::from("EyeLL2"));
+            let differences_only = compare.differences_only;
+            if let Some(entry) = entries.get_mut(state.selected_material_index)
+                && let Some(other) = other
+            {
+                ScrollArea::vertical()
+                    .auto_shrink([false; 2])
+                    .show(ui, |ui| {
+                        changed |= compare_materials(ui, entry, &other, differences_only);
+                    });
+            }
+        });
+
+    (open, changed)
+}
+
+fn material_usage_window(
+    state: &mut MatlEditorState,
+    ctx: &egui::Context,
+    entries: &[MatlEntryData],
+    modl: Option<&ModlData>,
+    anims: &[(String, Option<AnimData>)],
+    rules: &[MaterialVariantRule],
+) {
+    Window::new("Material Usage")
+        .open(&mut state.usage_window_open)
+        .resizable(true)
+        .show(ctx, |ui| {
+            let Some(modl) = modl else {
+                ui.label(NO_MODL_USAGE);
+                return;
+            };
+
+            ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    Grid::new("material_usage")
+                        .num_columns(3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.heading("Material");
+                            ui.heading("Mesh Objects");
+                            ui.heading("Material Animations");
+                            ui.end_row();
+
+                            for entry in entries {
+                                let usage = material_usage(
+                                    &entry.material_label,
+                                    &modl.entries,
+                                    anims,
+                                    rules,
+                                );
+
+                                if usage.is_unused() {
+                                    ui.label(warning_icon_text(&entry.material_label))
+                                        .on_hover_text(UNUSED_MATERIAL);
+                                } else {
+                                    ui.label(&entry.material_label);
+                                }
+
+                                ui.vertical(|ui| {
+                                    if let Some(label) = &usage.variant_of {
+                                        ui.weak(format!("Variant of {label}"));
+                                    }
+                                    for (name, subindex) in &usage.mesh_objects {
+                                        ui.label(format!("{name} ({subindex})"));
+                                    }
+                                });
+
+                                ui.vertical(|ui| {
+                                    for (file, track) in &usage.anim_tracks {
+                                        ui.label(format!("{file}: {track}"));
+                                    }
+                                });
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+}
+
+fn auto_assign_textures_window(
+    state: &mut MatlEditorState,
+    ctx: &egui::Context,
+    entries: &mut [MatlEntryData],
+    folder_thumbnails: &[Thumbnail],
+    default_thumbnails: &[Thumbnail],
+    rules: &mut Vec<TextureSuffixRule>,
+) -> (bool, bool) {
+    let mut open = state.auto_assign_window_open;
+    let mut changed = false;
+    Window::new("Auto-assign Textures")
+        .open(&mut open)
+        .resizable(true)
+        .show(ctx, |ui| {
+            CollapsingHeader::new(RichText::new("Rules").heading())
+                .default_open(false)
+                .show(ui, |ui| {
+                    edit_texture_suffix_rules(ui, rules);
+                });
+            ui.separator();
+
+            ui.checkbox(&mut state.auto_assign_all_materials, "All Materials")
+                .on_hover_text(
+                    "Assign textures for all materials instead of the selected material.",
+                );
+
+            // Material parameters don't include the .nutexb extension.
+            let names: Vec<_> = folder_thumbnails
+                .iter()
+                .chain(default_thumbnails.iter())
+                .map(|(name, _, _)| {
+                    Path::new(name)
+                        .with_extension("")
+                        .to_string_lossy()
+                        .to_string()
+                })
+                .collect();
+            let names: Vec<_> = names.iter().map(|n| n.as_str()).collect();
+
+            let assignments: Vec<_> = entries
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| {
+                    state.auto_assign_all_materials || *i == state.selected_material_index
+                })
+                .map(|(i, e)| (i, auto_assign_textures(e, &names, rules)))
+                .filter(|(_, a)| !a.is_empty())
+                .collect();
+
+            if ui
+                .add_enabled(!assignments.is_empty(), Button::new("Apply"))
+                .clicked()
+            {
+                for (i, entry_assignments) in &assignments {
+                    for assignment in entry_assignments {
+                        if let Some(param) = entries[*i]
+                            .textures
+                            .iter_mut()
+                            .find(|p| p.param_id == assignment.param_id)
+                        {
+                            param.data.clone_from(&assignment.new_texture);
+                        }
+                    }
+                }
+                changed = true;
+            }
+            horizontal_separator_empty(ui);
+
+            if assignments.is_empty() {
+                ui.label("No texture changes found.");
+            }
+
+            ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    Grid::new("auto_assign_grid").striped(true).show(ui, |ui| {
+                        ui.heading("Material");
+                        ui.heading("Parameter");
+                        ui.heading("Current");
+                        ui.heading("New");
+                        ui.end_row();
+
+                        for (i, entry_assignments) in &assignments {
+                            for assignment in entry_assignments {
+                                ui.label(&entries[*i].material_label);
+                                ui.label(param_label(assignment.param_id));
+                                ui.label(&assignment.old_texture);
+                                ui.label(&assignment.new_texture);
+                                ui.end_row();
+                            }
+                        }
+                    });
+                });
+        });
+
+    (open, changed)
+}
+
+fn edit_texture_suffix_rules(ui: &mut Ui, rules: &mut Vec<TextureSuffixRule>) {
+    let mut index_to_remove = None;
+
+    Grid::new("texture_suffix_rules").show(ui, |ui| {
+        ui.label("Suffix");
+        ui.label("Parameter");
+        ui.end_row();
+
+        for (i, rule) in rules.iter_mut().enumerate() {
+            ui.add(TextEdit::singleline(&mut rule.suffix).desired_width(100.0));
+            ComboBox::from_id_salt(egui::Id::new("suffix_rule").with(i))
+                .selected_text(rule.param_id.to_string())
+                .show_ui(ui, |ui| {
+                    for param_id in ParamId::iter().filter(|p| is_texture(*p)) {
+                        ui.selectable_value(&mut rule.param_id, param_id, param_label(param_id));
+                    }
+                });
+            if ui.button("Remove").clicked() {
+                index_to_remove = Some(i);
+            }
+            ui.end_row();
+        }
+    });
+
+    if let Some(i) = index_to_remove {
+        rules.remove(i);
     }
 
-    if visited.contains(&String::from("EyeR2")) {
-        //World of Light purple
-        visited.push(String::from("EyeRD2"));
-        //Final Smash
-        visited.push(String::from("EyeRG2"));
-        //World of Light red
-        visited.push(String::from("EyeRL2"));
+    ui.horizontal(|ui| {
+        if ui.button("Add Rule").clicked() {
+            rules.push(TextureSuffixRule::new("", ParamId::Texture0));
+        }
+
+        if ui.button("Reset Rules").clicked() {
+            *rules = default_texture_suffix_rules();
+        }
+    });
+}
+
+fn compare_materials(
+    ui: &mut Ui,
+    entry: &mut MatlEntryData,
+    other: &MatlEntryData,
+    differences_only: bool,
+) -> bool {
+    let mut changed = false;
+
+    let diffs = diff_materials(entry, other);
+
+    let copy_count = diffs
+        .iter()
+        .filter(|(_, d)| matches!(d, ParamDiff::Different | ParamDiff::SecondOnly))
+        .count();
+    if ui
+        .add_enabled(copy_count > 0, Button::new("Copy All Differences"))
+        .on_hover_text("Copy all changed and missing parameters to the edited material.")
+        .clicked()
+    {
+        for (param_id, _) in &diffs {
+            copy_parameter(entry, other, *param_id);
+        }
+        changed = true;
     }
 
-    matl_entries.retain(|item| visited.contains(&item.material_label));
+    Grid::new("compare_grid")
+        .num_columns(4)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.heading("Parameter");
+            ui.heading(&entry.material_label);
+            ui.heading(&other.material_label);
+            ui.label("");
+            ui.end_row();
+
+            if !differences_only || entry.shader_label != other.shader_label {
+                let text = RichText::new("Shader Label");
+                if entry.shader_label != other.shader_label {
+                    ui.label(text.color(WARNING_COLOR));
+                } else {
+                    ui.label(text);
+                }
+                ui.label(&entry.shader_label);
+                ui.label(&other.shader_label);
+                if entry.shader_label != other.shader_label {
+                    if ui.button("Copy").clicked() {
+                        entry.shader_label = other.shader_label.clone();
+                        changed = true;
+                    }
+                } else {
+                    ui.label("");
+                }
+                ui.end_row();
+            }
+
+            for (param_id, diff) in diffs {
+                if differences_only && diff == ParamDiff::Equal {
+                    continue;
+                }
+
+                let text = RichText::new(param_label(param_id));
+                match diff {
+                    ParamDiff::Equal => ui.label(text),
+                    ParamDiff::Different => ui.label(text.color(WARNING_COLOR)),
+                    ParamDiff::FirstOnly | ParamDiff::SecondOnly => ui
+                        .label(text.color(ERROR_COLOR))
+                        .on_hover_text("This parameter is missing from one of the materials."),
+                };
+
+                let missing = || RichText::new("Missing").weak();
+                match param_value_text(entry, param_id) {
+                    Some(value) => ui.label(value),
+                    None => ui.label(missing()),
+                };
+                match param_value_text(other, param_id) {
+                    Some(value) => ui.label(value),
+                    None => ui.label(missing()),
+                };
+
+                if matches!(diff, ParamDiff::Different | ParamDiff::SecondOnly) {
+                    if ui
+                        .button("Copy")
+                        .on_hover_text("Copy this value to the edited material.")
+                        .clicked()
+                    {
+                        changed |= copy_parameter(entry, other, param_id);
+                    }
+                } else {
+                    ui.label("");
+                }
+                ui.end_row();
+            }
+        });
+
+    changed
 }
 
-fn load_presets_from_file<F: Fn(&[u8]) -> anyhow::Result<Vec<MatlEntryData>>>(
-    presets: &mut Vec<MatlEntryData>,
-    file: std::path::PathBuf,
-    load_presets: F,
-) {
-    match std::fs::read(&file)
-        .map_err(|e| {
-            error!("Error reading presets file {file:?}: {e}");
-            e.into()
+fn param_value_text(entry: &MatlEntryData, param_id: ParamId) -> Option<String> {
+    fn text<T: MatlParam>(
+        entry: &MatlEntryData,
+        param_id: ParamId,
+        f: impl Fn(&T) -> String,
+    ) -> Option<String> {
+        find_param::<T>(entry, param_id).map(|p| f(&p.data))
+    }
+
+    text::<bool>(entry, param_id, |d| d.to_string())
+        .or_else(|| text::<f32>(entry, param_id, |d| d.to_string()))
+        .or_else(|| {
+            text::<Vector4>(entry, param_id, |d| {
+                format!("{}, {}, {}, {}", d.x, d.y, d.z, d.w)
+            })
+        })
+        .or_else(|| text::<String>(entry, param_id, |d| d.clone()))
+        .or_else(|| {
+            text::<SamplerData>(entry, param_id, |d| {
+                format!(
+                    "Wrap: {}, {}, {}\nFilter: {}, {}, {}\nBorder Color: {}, {}, {}, {}\nLod Bias: {}",
+                    d.wraps,
+                    d.wrapt,
+                    d.wrapr,
+                    d.min_filter,
+                    d.mag_filter,
+                    anisotropy_label(d.max_anisotropy),
+                    d.border_color.r,
+                    d.border_color.g,
+                    d.border_color.b,
+                    d.border_color.a,
+                    d.lod_bias
+                )
+            })
+        })
+        .or_else(|| {
+            text::<UvTransform>(entry, param_id, |d| {
+                format!(
+                    "Scale: {}, {}\nRotation: {}\nTranslate: {}, {}",
+                    d.scale_u, d.scale_v, d.rotation, d.translate_u, d.translate_v
+                )
+            })
+        })
+        .or_else(|| {
+            text::<RasterizerStateData>(entry, param_id, |d| {
+                format!(
+                    "Fill: {}\nCull: {}\nDepth Bias: {}",
+                    d.fill_mode, d.cull_mode, d.depth_bias
+                )
+            })
+        })
+        .or_else(|| {
+            text::<BlendStateData>(entry, param_id, |d| {
+                format!(
+                    "Source Color: {}\nColor Operation: {}\nDestination Color: {}\nSource Alpha: {}\nAlpha Operation: {}\nDestination Alpha: {}\nAlpha Sample to Coverage: {}",
+                    d.source_color,
+                    d.color_operation,
+                    d.destination_color,
+                    d.source_alpha,
+                    d.alpha_operation,
+                    d.destination_alpha,
+                    d.alpha_sample_to_coverage
+                )
+            })
         })
-        .and_then(|bytes| load_presets(&bytes))
-    {
-        Ok(new_presets) => presets.extend(new_presets),
-        Err(e) => error!("Error importing presets: {e}"),
-    }
-}
-
-fn save_material_presets(presets: &[MatlEntryData], file: std::path::PathBuf) {
-    // TODO: Give a visual indication that the file saved?
-    match serde_json::to_string_pretty(&MatlData {
-        major_version: 1,
-        minor_version: 6,
-        entries: presets.to_vec(),
-    }) {
-        Ok(presets_json) => {
-            if let Err(e) = std::fs::write(file, presets_json) {
-                error!("Failed to save material presets JSON: {e}");
-            }
-        }
-        Err(e) => error!("Failed to convert material presets to JSON: {e}"),
-    }
 }
 
 fn edit_matl_entry(
@@ -490,7 +1300,7 @@ fn edit_matl_entry(
             })
             .unwrap_or_default();
 
-        changed |= edit_material_label(entry, ui, &mut modl_entries);
+        changed |= edit_material_label(entry, ui, &mut modl_entries, state);
     });
 
     changed |= edit_matl_entry_inner(
@@ -512,15 +1322,15 @@ fn edit_matl_entry(
 fn preset_window(
     state: &mut MatlEditorState,
     ctx: &egui::Context,
-    material_presets: &[MatlEntryData],
-    default_presets: &[MatlEntryData],
+    material_presets: &[MaterialPreset],
+    default_presets: &[MaterialPreset],
     entry: Option<&mut MatlEntryData>,
     shader_database: &ShaderDatabase,
 ) -> (bool, bool) {
     let mut open = state.matl_preset_window_open;
     let mut changed = false;
     Window::new("Select Material Preset")
-        .open(&mut state.matl_preset_window_open)
+        .open(&mut open)
         .resizable(true)
         .show(ctx, |ui| {
             Panel::bottom("preset_bottom").show(ui, |ui| {
@@ -535,75 +1345,206 @@ fn preset_window(
                         if let Some(preset) = presets.get(state.selected_preset_index)
                             && let Some(entry) = entry
                         {
-                            *entry = apply_preset(entry, preset);
+                            *entry = apply_preset(entry, &preset.material);
                             changed = true;
                         }
-                        open = false;
+                        state.matl_preset_window_open = false;
                     }
                 });
             });
 
             CentralPanel::default().show(ui, |ui| {
-                ScrollArea::vertical()
-                    .auto_shrink([false; 2])
-                    .show(ui, |ui| {
-                        ui.horizontal(|ui| {
-                            ui.selectable_value(
-                                &mut state.preset_mode,
-                                PresetMode::Default,
-                                RichText::new("Default").heading(),
-                            );
-                            ui.selectable_value(
-                                &mut state.preset_mode,
-                                PresetMode::User,
-                                RichText::new("User").heading(),
-                            );
+                ui.horizontal(|ui| {
+                    ui.selectable_value(
+                        &mut state.preset_mode,
+                        PresetMode::Default,
+                        RichText::new("Default").heading(),
+                    );
+                    ui.selectable_value(
+                        &mut state.preset_mode,
+                        PresetMode::User,
+                        RichText::new("User").heading(),
+                    );
+                });
+
+                Grid::new("preset_filter").num_columns(2).show(ui, |ui| {
+                    ui.label("Search");
+                    ui.add(
+                        TextEdit::singleline(&mut state.preset_search)
+                            .hint_text("Name, description, tags, or shader"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Category");
+                    ComboBox::from_id_salt("preset_category_filter")
+                        .width(200.0)
+                        .selected_text(
+                            state
+                                .preset_category
+                                .map(|c| c.to_string())
+                                .unwrap_or_else(|| "All".to_owned()),
+                        )
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut state.preset_category, None, "All");
+                            for c in PresetCategory::iter() {
+                                ui.selectable_value(
+                                    &mut state.preset_category,
+                                    Some(c),
+                                    c.to_string(),
+                                );
+                            }
                         });
+                    ui.end_row();
+                });
 
-                        ui.weak("Hover over a preset to see shader info.");
-                        horizontal_separator_empty(ui);
+                ui.weak("Hover over a preset to see shader info.");
+                horizontal_separator_empty(ui);
 
-                        match state.preset_mode {
-                            PresetMode::User => {
-                                if material_presets.is_empty() {
-                                    ui.label(NO_USER_PRESETS);
-                                } else {
-                                    list_presets(
-                                        ui,
-                                        material_presets,
-                                        &mut state.selected_preset_index,
-                                        shader_database,
-                                    );
-                                }
+                ScrollArea::vertical()
+                    .auto_shrink([false; 2])
+                    .show(ui, |ui| match state.preset_mode {
+                        PresetMode::User => {
+                            if material_presets.is_empty() {
+                                ui.label(NO_USER_PRESETS);
+                            } else {
+                                list_presets(ui, material_presets, state, shader_database);
                             }
-                            PresetMode::Default => list_presets(
-                                ui,
-                                default_presets,
-                                &mut state.selected_preset_index,
-                                shader_database,
-                            ),
+                        }
+                        PresetMode::Default => {
+                            list_presets(ui, default_presets, state, shader_database)
                         }
                     });
             });
         });
 
-    (open, changed)
+    // The Apply button closes the window by clearing the state flag.
+    (open && state.matl_preset_window_open, changed)
 }
 
 fn list_presets(
     ui: &mut Ui,
-    material_presets: &[MatlEntryData],
-    selected_index: &mut usize,
+    material_presets: &[MaterialPreset],
+    state: &mut MatlEditorState,
     shader_database: &ShaderDatabase,
 ) {
+    let mut any_visible = false;
     for (i, preset) in material_presets.iter().enumerate() {
-        let response = ui.selectable_value(selected_index, i, &preset.material_label);
-        if let Some(program) = shader_database.get(&preset.shader_label) {
-            let tooltip = program_attributes(program);
-            if !tooltip.is_empty() {
-                response.on_hover_text(tooltip);
-            }
+        if !preset.matches_search(&state.preset_search)
+            || state.preset_category.is_some_and(|c| c != preset.category)
+        {
+            continue;
         }
+        any_visible = true;
+
+        ui.horizontal(|ui| {
+            if let Some(path) = &preset.thumbnail
+                && let Some(texture) =
+                    preset_thumbnail(ui.ctx(), &mut state.preset_thumbnails, path)
+            {
+                ui.image((texture.id(), egui::Vec2::new(48.0, 48.0)));
+            }
+
+            ui.vertical(|ui| {
+                let response = ui.selectable_value(
+                    &mut state.selected_preset_index,
+                    i,
+                    RichText::new(&preset.name).strong(),
+                );
+                if let Some(program) = shader_database.get(&preset.material.shader_label) {
+                    let tooltip = program_attributes(program);
+                    if !tooltip.is_empty() {
+                        response.on_hover_text(tooltip);
+                    }
+                }
+
+                let mut info = preset.category.to_string();
+                if !preset.tags.is_empty() {
+                    info.push_str(" | ");
+                    info.push_str(&preset.tags.join(", "));
+                }
+                ui.weak(info);
+
+                if !preset.description.is_empty() {
+                    ui.label(&preset.description);
+                }
+            });
+        });
+    }
+
+    if !any_visible {
+        ui.label(NO_MATCHING_PRESETS);
+    }
+}
+
+fn preset_thumbnail(
+    ctx: &egui::Context,
+    thumbnails: &mut HashMap<String, Option<egui::TextureHandle>>,
+    path: &str,
+) -> Option<egui::TextureHandle> {
+    // Cache failed loads to avoid reading the file and logging errors every frame.
+    thumbnails
+        .entry(path.to_string())
+        .or_insert_with(|| match image::open(path) {
+            Ok(image) => {
+                let image = image.into_rgba8();
+                let size = [image.width() as usize, image.height() as usize];
+                let color_image = egui::ColorImage::from_rgba_unmultiplied(size, image.as_raw());
+                Some(ctx.load_texture(path, color_image, egui::TextureOptions::default()))
+            }
+            Err(e) => {
+                error!("Failed to load preset thumbnail {path:?}: {e}");
+                None
+            }
+        })
+        .clone()
+}
+
+fn new_preset_window(
+    state: &mut MatlEditorState,
+    ctx: &egui::Context,
+    material_presets: &mut Vec<MaterialPreset>,
+) {
+    let mut open = state.new_preset.is_some();
+    let mut added = false;
+    if let Some(preset) = &mut state.new_preset {
+        Window::new("Add Material to Presets")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                Grid::new("new_preset").num_columns(2).show(ui, |ui| {
+                    ui.label("Name");
+                    ui.text_edit_singleline(&mut preset.name);
+                    ui.end_row();
+
+                    ui.label("Description");
+                    ui.add(TextEdit::multiline(&mut preset.description).desired_rows(2));
+                    ui.end_row();
+
+                    ui.label("Category");
+                    preset_category_combo(ui, "new_preset_category", &mut preset.category);
+                    ui.end_row();
+
+                    ui.label("Tags");
+                    ui.vertical(|ui| {
+                        edit_preset_tags(ui, &mut preset.tags, &mut state.new_preset_tag);
+                    });
+                    ui.end_row();
+                });
+
+                horizontal_separator_empty(ui);
+                if ui
+                    .add_enabled(!preset.name.trim().is_empty(), Button::new("Add"))
+                    .clicked()
+                {
+                    material_presets.push(preset.clone());
+                    added = true;
+                }
+            });
+    }
+
+    if !open || added {
+        state.new_preset = None;
+        state.new_preset_tag.clear();
     }
 }
 
@@ -634,6 +1575,7 @@ fn shader_finder_window(
     state: &mut MatlEditorState,
     ctx: &egui::Context,
     entry: Option<&mut MatlEntryData>,
+    mesh: Option<&MeshData>,
     shader_database: &ShaderDatabase,
 ) -> (bool, bool) {
     let mut open = state.shader_finder_window_open;
@@ -665,6 +1607,12 @@ fn shader_finder_window(
                     ScrollArea::vertical()
                         .auto_shrink([false; 2])
                         .show(ui, |ui| {
+                            CollapsingHeader::new("Mesh Object")
+                                .default_open(true)
+                                .show(ui, |ui| {
+                                    shader_finder_mesh(ui, &mut state.shader_finder, mesh);
+                                });
+
                             CollapsingHeader::new("Custom Booleans").show(ui, |ui| {
                                 shader_finder_booleans(ui, &mut state.shader_finder);
                             });
@@ -704,26 +1652,47 @@ fn shader_finder_window(
                     .auto_shrink([false; 2])
                     .show(ui, |ui| {
                         // The database uses a sorted map, so we don't need to sort by name.
-                        for (name, shader) in &shader_database.0 {
-                            if should_include_shader(&state.shader_finder, shader) {
-                                ui.horizontal(|ui| {
-                                    // TODO: Center these vertically?
-                                    shader_complexity_color(ui, shader.complexity).on_hover_text(
-                                        format!("Shader Complexity: {:.3}", shader.complexity),
-                                    );
-
-                                    ui.selectable_value(
-                                        &mut state.shader_finder.selected_shader,
-                                        name.to_string(),
-                                        name,
-                                    )
-                                    .on_hover_ui(|ui| {
-                                        for param in &shader.material_parameters {
-                                            ui.label(param);
-                                        }
-                                    });
+                        let mut shaders: Vec<_> = shader_database
+                            .0
+                            .iter()
+                            .filter(|(_, shader)| {
+                                should_include_shader(&state.shader_finder, shader)
+                            })
+                            .collect();
+
+                        let mesh_object = state
+                            .shader_finder
+                            .mesh_object_index
+                            .and_then(|i| mesh?.objects.get(i));
+                        if let Some(mesh_object) = mesh_object {
+                            // Rank the shaders the mesh already supports from cheapest to most expensive.
+                            let attribute_names = mesh_attribute_names(mesh_object);
+                            shaders.retain(|(_, shader)| {
+                                shader
+                                    .missing_required_attributes(&attribute_names)
+                                    .is_empty()
+                            });
+                            shaders.sort_by(|(_, a), (_, b)| a.complexity.total_cmp(&b.complexity));
+                        }
+
+                        for (name, shader) in shaders {
+                            ui.horizontal(|ui| {
+                                // TODO: Center these vertically?
+                                shader_complexity_color(ui, shader.complexity).on_hover_text(
+                                    format!("Shader Complexity: {:.3}", shader.complexity),
+                                );
+
+                                ui.selectable_value(
+                                    &mut state.shader_finder.selected_shader,
+                                    name.to_string(),
+                                    name,
+                                )
+                                .on_hover_ui(|ui| {
+                                    for param in &shader.material_parameters {
+                                        ui.label(param);
+                                    }
                                 });
-                            }
+                            });
                         }
                     });
             });
@@ -765,6 +1734,39 @@ fn shader_finder_shader(ui: &mut Ui, state: &mut ShaderFinderState) {
         .on_hover_text(ANISOTROPIC_ROTATION_DESCRIPTION);
 }
 
+fn shader_finder_mesh(ui: &mut Ui, state: &mut ShaderFinderState, mesh: Option<&MeshData>) {
+    let objects = mesh.map(|m| m.objects.as_slice()).unwrap_or_default();
+    let object_name = |i: usize| {
+        objects
+            .get(i)
+            .map(|o| format!("{} ({})", o.name, o.subindex))
+            .unwrap_or_default()
+    };
+
+    ComboBox::from_id_salt("shader_finder_mesh")
+        .width(200.0)
+        .selected_text(
+            state
+                .mesh_object_index
+                .map(object_name)
+                .unwrap_or_else(|| "None".to_owned()),
+        )
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut state.mesh_object_index, None, "None");
+            for i in 0..objects.len() {
+                ui.selectable_value(&mut state.mesh_object_index, Some(i), object_name(i));
+            }
+        })
+        .response
+        .on_hover_text(
+            "Only show shaders with required attributes present in the mesh, sorted by complexity.",
+        );
+
+    if let Some(o) = state.mesh_object_index.and_then(|i| objects.get(i)) {
+        ui.label(mesh_attribute_names(o).join(", "));
+    }
+}
+
 fn shader_finder_attributes(ui: &mut Ui, state: &mut ShaderFinderState) {
     ui.checkbox(&mut state.color_set1, "colorSet1");
     ui.checkbox(&mut state.color_set2, "colorSet2");
@@ -868,12 +1870,14 @@ fn has_params(has_params: &[bool], shader: &ShaderProgram, prefix: &str) -> bool
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 fn menu_bar(
     ui: &mut Ui,
     matl: &mut MatlData,
     modl: &Option<&mut ModlData>,
     state: &mut MatlEditorState,
-    material_presets: &mut Vec<MatlEntryData>,
+    clipboard: &mut Option<MatlEntryData>,
+    material_variant_rules: &[MaterialVariantRule],
     folder_name: &Path,
     file_name: &str,
 ) -> (bool, bool) {
@@ -917,11 +1921,10 @@ fn menu_bar(
             }
             ui.separator();
 
-            if ui.button("Add Material to Presets").clicked() {
-                // TODO: Prompt for naming the preset?
-                if let Some(entry) = matl.entries.get(state.selected_material_index) {
-                    material_presets.push(entry.clone());
-                }
+            if ui.button("Add Material to Presets").clicked()
+                && let Some(entry) = matl.entries.get(state.selected_material_index)
+            {
+                state.new_preset = Some(MaterialPreset::new(entry.clone()));
             }
 
             if ui.button("Apply Preset").clicked() {
@@ -932,6 +1935,18 @@ fn menu_bar(
             if ui.button("Find Shader").clicked() {
                 state.shader_finder_window_open = true;
             }
+
+            if ui.button("Compare Materials").clicked() {
+                state.compare_window_open = true;
+            }
+
+            if ui.button("Auto-assign Textures").clicked() {
+                state.auto_assign_window_open = true;
+            }
+
+            if ui.button("Material Usage").clicked() {
+                state.usage_window_open = true;
+            }
             ui.separator();
 
             if ui.button("Remove Duplicates").clicked() {
@@ -944,11 +1959,15 @@ fn menu_bar(
                 .clicked()
                 && let Some(modl) = modl
             {
-                remove_unused_materials(&mut matl.entries, &modl.entries);
+                remove_unused_materials(&mut matl.entries, &modl.entries, material_variant_rules);
                 changed = true;
             }
         });
 
+        ui.menu_button("Clipboard", |ui| {
+            changed |= clipboard_menu(ui, matl, state, clipboard);
+        });
+
         ui.menu_button("Reorder", |ui| {
             if ui.button("Move Material to Top").clicked() {
                 matl.entries.swap(0, state.selected_material_index);
@@ -984,6 +2003,73 @@ fn menu_bar(
     (changed, saved)
 }
 
+fn clipboard_menu(
+    ui: &mut Ui,
+    matl: &mut MatlData,
+    state: &mut MatlEditorState,
+    clipboard: &mut Option<MatlEntryData>,
+) -> bool {
+    let mut changed = false;
+
+    if let Some(entry) = matl.entries.get(state.selected_material_index) {
+        if ui.button("Copy Material").clicked() {
+            copy_material(ui.ctx(), clipboard, entry.clone());
+        }
+
+        ui.menu_button("Copy Parameters", |ui| {
+            for group in ParamGroup::iter() {
+                let param_ids = group.param_ids(entry);
+                ui.add_enabled_ui(!param_ids.is_empty(), |ui| {
+                    ui.menu_button(group.name(), |ui| {
+                        if ui.button(format!("Copy All {}", group.name())).clicked() {
+                            copy_material(
+                                ui.ctx(),
+                                clipboard,
+                                filter_parameters(entry, &param_ids),
+                            );
+                        }
+                        ui.separator();
+
+                        for param_id in &param_ids {
+                            if ui.button(param_label(*param_id)).clicked() {
+                                copy_material(
+                                    ui.ctx(),
+                                    clipboard,
+                                    filter_parameters(entry, &[*param_id]),
+                                );
+                            }
+                        }
+                    });
+                });
+            }
+        });
+    }
+    ui.separator();
+
+    if ui
+        .add_enabled(clipboard.is_some(), Button::new("Paste Parameters"))
+        .on_hover_text("Add or replace the copied parameters in the selected material.")
+        .clicked()
+        && let Some(entry) = matl.entries.get_mut(state.selected_material_index)
+        && let Some(source) = clipboard
+    {
+        paste_parameters(entry, source);
+        changed = true;
+    }
+
+    if ui
+        .add_enabled(clipboard.is_some(), Button::new("Paste as New Material"))
+        .clicked()
+        && let Some(source) = clipboard
+    {
+        matl.entries.push(source.clone());
+        state.selected_material_index = matl.entries.len() - 1;
+        changed = true;
+    }
+
+    changed
+}
+
 fn help_menu(ui: &mut Ui) {
     ui.menu_button("Help", |ui| {
         let button = |ui: &mut Ui, text: &str| ui.add(Button::new(text).wrap_mode(TextWrapMode::Extend));
@@ -1015,6 +2101,7 @@ fn edit_material_label(
     entry: &mut MatlEntryData,
     ui: &mut Ui,
     modl_entries: &mut [&mut ModlEntryData],
+    state: &mut MatlEditorState,
 ) -> bool {
     // TODO: Get this to work with lost_focus for efficiency.
     // TODO: Show errors if these checks fail?
@@ -1023,6 +2110,18 @@ fn edit_material_label(
         egui::TextEdit::singleline(&mut entry.material_label),
     );
 
+    // Variant materials are renamed once editing finishes.
+    // Intermediate labels while typing won't match any variant rules.
+    if response.gained_focus() {
+        state.material_label_before_edit = Some(entry.material_label.clone());
+    }
+    if response.lost_focus()
+        && let Some(old_label) = state.material_label_before_edit.take()
+        && old_label != entry.material_label
+    {
+        state.renamed_material_label = Some((old_label, entry.material_label.clone()));
+    }
+
     let changed = response.changed();
     if changed {
         // Rename any effected modl entries if the material label changes.
@@ -1256,6 +2355,401 @@ fn edit_matl_entry_inner(
     changed
 }
 
+fn edit_matl_entries_batch(
+    ui: &mut Ui,
+    entries: &mut [&mut MatlEntryData],
+    add_missing: &mut bool,
+) -> bool {
+    let mut changed = false;
+
+    ui.heading(format!("{} Materials Selected", entries.len()));
+    let labels: Vec<_> = entries.iter().map(|e| e.material_label.as_str()).collect();
+    ui.label(labels.join(", "));
+    ui.checkbox(add_missing, "Add Missing Parameters")
+        .on_hover_text("Add edited parameters to selected materials that don't have them.");
+    horizontal_separator_empty(ui);
+
+    ui.heading("Parameters");
+    let add_missing = *add_missing;
+
+    Grid::new("batch_booleans").num_columns(2).show(ui, |ui| {
+        let param_ids = batch_param_ids::<bool>(&entry_refs(entries));
+        for param_id in param_ids {
+            ui.label(batch_param_text::<bool>(&entry_refs(entries), param_id));
+            changed |= edit_batch_value::<bool, _>(
+                ui,
+                entries,
+                param_id,
+                add_missing,
+                |d| d,
+                |d| d,
+                |ui, value| ui.checkbox(value, "").changed(),
+            );
+            ui.end_row();
+        }
+    });
+    horizontal_separator_empty(ui);
+
+    Grid::new("batch_floats").num_columns(2).show(ui, |ui| {
+        let param_ids = batch_param_ids::<f32>(&entry_refs(entries));
+        for param_id in param_ids {
+            let id = egui::Id::new("batch").with(param_id.to_string());
+            ui.label(batch_param_text::<f32>(&entry_refs(entries), param_id));
+            changed |= edit_batch_value::<f32, _>(
+                ui,
+                entries,
+                param_id,
+                add_missing,
+                |d| d,
+                |d| d,
+                |ui, value| ui.add(DragSlider::new(id, value)).changed(),
+            );
+            ui.end_row();
+        }
+    });
+    horizontal_separator_empty(ui);
+
+    Grid::new("batch_vectors").num_columns(2).show(ui, |ui| {
+        let param_ids = batch_param_ids::<Vector4>(&entry_refs(entries));
+        for param_id in param_ids {
+            let id = egui::Id::new("batch").with(param_id.to_string());
+            let labels = vector4_labels_short(param_id);
+            let labels_long = vector4_labels_long(param_id);
+
+            ui.label(batch_param_text::<Vector4>(&entry_refs(entries), param_id));
+            ui.horizontal(|ui| {
+                // Edit components separately to preserve other components with mixed values.
+                let mut edit_component =
+                    |ui: &mut Ui,
+                     i: usize,
+                     field: fn(&Vector4) -> &f32,
+                     field_mut: fn(&mut Vector4) -> &mut f32| {
+                        ui.add_sized([15.0, 20.0], Label::new(labels[i]));
+                        changed |= edit_batch_value(
+                            ui,
+                            entries,
+                            param_id,
+                            add_missing,
+                            field,
+                            field_mut,
+                            |ui, value| {
+                                ui.add(DragSlider::new(id.with(i), value).width(50.0))
+                                    .on_hover_text(labels_long[i])
+                                    .changed()
+                            },
+                        );
+                    };
+                edit_component(ui, 0, |d| &d.x, |d| &mut d.x);
+                edit_component(ui, 1, |d| &d.y, |d| &mut d.y);
+                edit_component(ui, 2, |d| &d.z, |d| &mut d.z);
+                edit_component(ui, 3, |d| &d.w, |d| &mut d.w);
+            });
+            ui.end_row();
+        }
+    });
+    horizontal_separator_empty(ui);
+
+    Grid::new("batch_textures").num_columns(2).show(ui, |ui| {
+        let param_ids = batch_param_ids::<String>(&entry_refs(entries));
+        for param_id in param_ids {
+            ui.label(batch_param_text::<String>(&entry_refs(entries), param_id));
+            changed |= edit_batch_value::<String, _>(
+                ui,
+                entries,
+                param_id,
+                add_missing,
+                |d| d,
+                |d| d,
+                |ui, value| ui.text_edit_singleline(value).changed(),
+            );
+            ui.end_row();
+        }
+    });
+    horizontal_separator_empty(ui);
+
+    let param_ids = batch_param_ids::<SamplerData>(&entry_refs(entries));
+    for param_id in param_ids {
+        let id = egui::Id::new("batch").with(param_id.to_string());
+        let text = batch_param_text::<SamplerData>(&entry_refs(entries), param_id);
+        CollapsingHeader::new(text).id_salt(id).show(ui, |ui| {
+            Grid::new(id).num_columns(2).show(ui, |ui| {
+                changed |= edit_batch_sampler(ui, entries, param_id, add_missing, id);
+            });
+        });
+    }
+    horizontal_separator_empty(ui);
+
+    let param_ids = batch_param_ids::<RasterizerStateData>(&entry_refs(entries));
+    for param_id in param_ids {
+        let id = egui::Id::new("batch").with(param_id.to_string());
+        let text = batch_param_text::<RasterizerStateData>(&entry_refs(entries), param_id);
+        CollapsingHeader::new(text).id_salt(id).show(ui, |ui| {
+            Grid::new(id).num_columns(2).show(ui, |ui| {
+                ui.label("Polygon Fill");
+                changed |= edit_batch_value::<RasterizerStateData, _>(
+                    ui,
+                    entries,
+                    param_id,
+                    add_missing,
+                    |d| &d.fill_mode,
+                    |d| &mut d.fill_mode,
+                    |ui, value| enum_combo_box(ui, id.with("fill"), value),
+                );
+                ui.end_row();
+
+                ui.label("Cull Mode");
+                changed |= edit_batch_value::<RasterizerStateData, _>(
+                    ui,
+                    entries,
+                    param_id,
+                    add_missing,
+                    |d| &d.cull_mode,
+                    |d| &mut d.cull_mode,
+                    |ui, value| enum_combo_box(ui, id.with("cull"), value),
+                );
+                ui.end_row();
+
+                ui.label("Depth Bias");
+                changed |= edit_batch_value::<RasterizerStateData, _>(
+                    ui,
+                    entries,
+                    param_id,
+                    add_missing,
+                    |d| &d.depth_bias,
+                    |d| &mut d.depth_bias,
+                    |ui, value| ui.add(DragValue::new(value).speed(0.1)).changed(),
+                );
+                ui.end_row();
+            });
+        });
+    }
+    horizontal_separator_empty(ui);
+
+    let param_ids = batch_param_ids::<BlendStateData>(&entry_refs(entries));
+    for param_id in param_ids {
+        let id = egui::Id::new("batch").with(param_id.to_string());
+        let text = batch_param_text::<BlendStateData>(&entry_refs(entries), param_id);
+        CollapsingHeader::new(text)
+            .id_salt(id)
+            .default_open(true)
+            .show(ui, |ui| {
+                Grid::new(id).num_columns(2).show(ui, |ui| {
+                    ui.label("Source Color");
+                    changed |= edit_batch_value::<BlendStateData, _>(
+                        ui,
+                        entries,
+                        param_id,
+                        add_missing,
+                        |d| &d.source_color,
+                        |d| &mut d.source_color,
+                        |ui, value| enum_combo_box(ui, id.with("srccolor"), value),
+                    );
+                    ui.end_row();
+
+                    ui.label("Destination Color");
+                    changed |= edit_batch_value::<BlendStateData, _>(
+                        ui,
+                        entries,
+                        param_id,
+                        add_missing,
+                        |d| &d.destination_color,
+                        |d| &mut d.destination_color,
+                        |ui, value| enum_combo_box(ui, id.with("dstcolor"), value),
+                    );
+                    ui.end_row();
+
+                    ui.label("Alpha Sample to Coverage");
+                    changed |= edit_batch_value::<BlendStateData, _>(
+                        ui,
+                        entries,
+                        param_id,
+                        add_missing,
+                        |d| &d.alpha_sample_to_coverage,
+                        |d| &mut d.alpha_sample_to_coverage,
+                        |ui, value| ui.checkbox(value, "").changed(),
+                    );
+                    ui.end_row();
+                });
+            });
+    }
+
+    changed
+}
+
+fn edit_batch_sampler(
+    ui: &mut Ui,
+    entries: &mut [&mut MatlEntryData],
+    param_id: ParamId,
+    add_missing: bool,
+    id: egui::Id,
+) -> bool {
+    let mut changed = false;
+
+    ui.label("Wrap S");
+    changed |= edit_batch_value::<SamplerData, _>(
+        ui,
+        entries,
+        param_id,
+        add_missing,
+        |d| &d.wraps,
+        |d| &mut d.wraps,
+        |ui, value| enum_combo_box(ui, id.with("wraps"), value),
+    );
+    ui.end_row();
+
+    ui.label("Wrap T");
+    changed |= edit_batch_value::<SamplerData, _>(
+        ui,
+        entries,
+        param_id,
+        add_missing,
+        |d| &d.wrapt,
+        |d| &mut d.wrapt,
+        |ui, value| enum_combo_box(ui, id.with("wrapt"), value),
+    );
+    ui.end_row();
+
+    ui.label("Wrap R");
+    changed |= edit_batch_value::<SamplerData, _>(
+        ui,
+        entries,
+        param_id,
+        add_missing,
+        |d| &d.wrapr,
+        |d| &mut d.wrapr,
+        |ui, value| enum_combo_box(ui, id.with("wrapr"), value),
+    );
+    ui.end_row();
+
+    ui.label("Min Filter");
+    changed |= edit_batch_value::<SamplerData, _>(
+        ui,
+        entries,
+        param_id,
+        add_missing,
+        |d| &d.min_filter,
+        |d| &mut d.min_filter,
+        |ui, value| enum_combo_box(ui, id.with("minfilter"), value),
+    );
+    ui.end_row();
+
+    ui.label("Mag Filter");
+    changed |= edit_batch_value::<SamplerData, _>(
+        ui,
+        entries,
+        param_id,
+        add_missing,
+        |d| &d.mag_filter,
+        |d| &mut d.mag_filter,
+        |ui, value| enum_combo_box(ui, id.with("magfilter"), value),
+    );
+    ui.end_row();
+
+    ui.label("Border Color");
+    changed |= edit_batch_value::<SamplerData, _>(
+        ui,
+        entries,
+        param_id,
+        add_missing,
+        |d| &d.border_color,
+        |d| &mut d.border_color,
+        edit_color4f_rgba,
+    );
+    ui.end_row();
+
+    ui.label("Lod Bias");
+    changed |= edit_batch_value::<SamplerData, _>(
+        ui,
+        entries,
+        param_id,
+        add_missing,
+        |d| &d.lod_bias,
+        |d| &mut d.lod_bias,
+        |ui, value| ui.add(DragValue::new(value).speed(0.1)).changed(),
+    );
+    ui.end_row();
+
+    ui.label("Max Anisotropy");
+    changed |= edit_batch_value::<SamplerData, _>(
+        ui,
+        entries,
+        param_id,
+        add_missing,
+        |d| &d.max_anisotropy,
+        |d| &mut d.max_anisotropy,
+        |ui, value| {
+            let mut changed = false;
+            ComboBox::from_id_salt(id.with("anis"))
+                .width(ui.available_width())
+                .selected_text(anisotropy_label(*value))
+                .show_ui(ui, |ui| {
+                    for variant in MaxAnisotropy::iter() {
+                        changed |= ui
+                            .selectable_value(value, variant, anisotropy_label(variant))
+                            .changed();
+                    }
+                });
+            changed
+        },
+    );
+    ui.end_row();
+
+    changed
+}
+
+/// Edit `field` for `param_id` and apply any changes to all `entries`.
+fn edit_batch_value<T: MatlParam, U: PartialEq + Clone>(
+    ui: &mut Ui,
+    entries: &mut [&mut MatlEntryData],
+    param_id: ParamId,
+    add_missing: bool,
+    field: impl Fn(&T) -> &U,
+    field_mut: impl Fn(&mut T) -> &mut U,
+    edit: impl FnOnce(&mut Ui, &mut U) -> bool,
+) -> bool {
+    let Some(value) = batch_value(&entry_refs(entries), param_id, field) else {
+        ui.label("");
+        return false;
+    };
+
+    let is_mixed = value.is_mixed();
+    let mut value = value.into_value();
+
+    let changed = ui
+        .horizontal(|ui| {
+            if is_mixed {
+                ui.label(RichText::new("Mixed").weak()).on_hover_text(
+                    "This value differs between the selected materials. Editing applies the value to all selected materials.",
+                );
+            }
+            edit(ui, &mut value)
+        })
+        .inner;
+
+    if changed {
+        set_batch_value(entries, param_id, field_mut, &value, add_missing);
+    }
+
+    changed
+}
+
+fn entry_refs<'a>(entries: &'a [&mut MatlEntryData]) -> Vec<&'a MatlEntryData> {
+    entries.iter().map(|e| &**e).collect()
+}
+
+fn batch_param_text<T: MatlParam>(entries: &[&MatlEntryData], param_id: ParamId) -> String {
+    let missing_count = batch_missing_count::<T>(entries, param_id);
+    if missing_count > 0 {
+        format!(
+            "{} (missing from {missing_count} of {})",
+            param_label(param_id),
+            entries.len()
+        )
+    } else {
+        param_label(param_id)
+    }
+}
+
 fn edit_shader(
     ui: &mut Ui,
     entry: &mut MatlEntryData,
@@ -1,6 +1,7 @@
 use crate::{
     EditorMessage, EditorResponse,
     app::{MeshEditorState, display_validation_errors, icon::draggable_icon, warning_icon_text},
+    bounds::{bounding_box, bounding_sphere, mesh_object_points},
     horizontal_separator_empty,
     path::folder_editor_title,
     save_file, save_file_as,
@@ -19,9 +20,11 @@ use ssbh_data::{
         AttributeData, BoneInfluence, MeshObjectData, VectorData, VertexWeight, transform_points,
         transform_vectors,
     },
+    meshex_data::EntryFlags,
+    modl_data::ModlEntryData,
     prelude::*,
 };
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 pub fn mesh_editor(
     ctx: &egui::Context,
@@ -67,10 +70,39 @@ pub fn mesh_editor(
                             .pick_file()
                     {
                         match MeshData::from_file(&file) {
-                            Ok(reference) => match_mesh_order(mesh, &reference),
+                            Ok(reference) => {
+                                match_mesh_order(mesh, &reference);
+                                if state.match_entry_order {
+                                    message = Some(EditorMessage::MatchMeshOrder);
+                                }
+                                changed = true;
+                            }
                             Err(e) => error!("Failed to read {file:?}: {e}"),
                         }
                     }
+
+                    ui.checkbox(
+                        &mut state.match_entry_order,
+                        "Reorder Modl and MeshEx Entries",
+                    )
+                    .on_hover_text(
+                        "Reorder the model.numdlb entries and model.numshexb groups to match when reordering meshes.",
+                    );
+
+                    if ui
+                        .add(
+                            Button::new("Auto-number Subindices")
+                                .wrap_mode(TextWrapMode::Extend),
+                        )
+                        .on_hover_text(
+                            "Renumber subindices for each mesh name and update the model.numdlb and model.numshexb.",
+                        )
+                        .clicked()
+                    {
+                        // Other files in the folder also need to be updated.
+                        message = Some(EditorMessage::AutoNumberSubindices);
+                        changed = true;
+                    }
                 });
 
                 ui.menu_button("Help", |ui| {
@@ -117,6 +149,7 @@ pub fn mesh_editor(
                                 skel,
                                 state.selected_index,
                                 &errors,
+                                &mut state.attribute_fill_value,
                             );
                         }
                     });
@@ -221,6 +254,10 @@ fn select_mesh_object_dnd(
             .position(|i| *i == state.selected_index)
             .unwrap_or_default();
         changed = true;
+
+        if state.match_entry_order {
+            *message = Some(EditorMessage::MatchMeshOrder);
+        }
     }
 
     changed
@@ -232,6 +269,7 @@ fn edit_mesh_object(
     skel: Option<&SkelData>,
     i: usize,
     errors: &[&MeshValidationError],
+    fill_value: &mut [f32; 4],
 ) -> bool {
     let mut changed = false;
 
@@ -249,7 +287,9 @@ fn edit_mesh_object(
         ui.end_row();
 
         // TODO: Is it possible to edit the subindex without messing up influence assignments?
-        ui.label("Subindex");
+        ui.label("Subindex").on_hover_text(
+            "Use Mesh > Auto-number Subindices to renumber subindices and update the model.numdlb.",
+        );
         changed |= ui
             .add(egui::DragValue::new(&mut mesh_object.subindex))
             .changed();
@@ -275,6 +315,12 @@ fn edit_mesh_object(
         .changed();
     horizontal_separator_empty(ui);
 
+    ui.heading("Bounds").on_hover_text(
+        "Bounding information is recalculated from the vertex positions when saving.",
+    );
+    show_bounds(ui, mesh_object);
+    horizontal_separator_empty(ui);
+
     // TODO: Simplify this code?
     let attribute_error = errors.iter().find(|e| {
         matches!(
@@ -310,7 +356,7 @@ fn edit_mesh_object(
         .next()
         .unwrap_or_default();
 
-    changed |= edit_mesh_attributes(ui, mesh_object, missing_attributes);
+    changed |= edit_mesh_attributes(ui, mesh_object, missing_attributes, fill_value);
     horizontal_separator_empty(ui);
 
     let has_influence_errors = errors.iter().any(|e| {
@@ -384,6 +430,31 @@ fn edit_mesh_object(
     changed
 }
 
+fn show_bounds(ui: &mut Ui, mesh_object: &MeshObjectData) {
+    let points = mesh_object_points(mesh_object);
+    Grid::new("mesh_bounds_grid").show(ui, |ui| {
+        if let Some(sphere) = bounding_sphere(&points) {
+            ui.label("Sphere Center");
+            ui.label(format_components(&sphere.center));
+            ui.end_row();
+
+            ui.label("Sphere Radius");
+            ui.label(format!("{:.3}", sphere.radius));
+            ui.end_row();
+        }
+
+        if let Some(bounding_box) = bounding_box(&points) {
+            ui.label("Box Min");
+            ui.label(format_components(&bounding_box.min));
+            ui.end_row();
+
+            ui.label("Box Max");
+            ui.label(format_components(&bounding_box.max));
+            ui.end_row();
+        }
+    });
+}
+
 // TODO: Move this to ssbh_data?
 fn convert_parent_bone_to_influences(mesh_object: &mut MeshObjectData, skel: Option<&SkelData>) {
     // Weight vertices to parent bone.
@@ -438,6 +509,64 @@ fn match_mesh_order(mesh: &mut MeshData, reference: &MeshData) {
     })
 }
 
+/// Renumber the subindices for mesh objects with the same name starting from 0
+/// and update the entries in `modl` and `meshex` to preserve existing assignments.
+pub fn auto_number_subindices(
+    mesh: &mut MeshData,
+    modl: Option<&mut ModlData>,
+    meshex: Option<&mut MeshExData>,
+) {
+    // Mesh objects with duplicate subindices may map to multiple new subindices.
+    let mut new_subindices = HashMap::<(String, u64), Vec<u64>>::new();
+    let mut counts = HashMap::<String, u64>::new();
+    for o in &mut mesh.objects {
+        let count = counts.entry(o.name.clone()).or_default();
+
+        new_subindices
+            .entry((o.name.clone(), o.subindex))
+            .or_default()
+            .push(*count);
+
+        o.subindex = *count;
+        *count += 1;
+    }
+
+    // Adj entries reference the mesh object index, which doesn't change.
+    if let Some(modl) = modl {
+        let mut entries = Vec::new();
+        for entry in &modl.entries {
+            let key = (entry.mesh_object_name.clone(), entry.mesh_object_subindex);
+            match new_subindices.get(&key) {
+                Some(subindices) => {
+                    // Assign the material to all mesh objects that previously shared this subindex.
+                    entries.extend(subindices.iter().map(|s| ModlEntryData {
+                        mesh_object_subindex: *s,
+                        ..entry.clone()
+                    }));
+                }
+                None => entries.push(entry.clone()),
+            }
+        }
+        modl.entries = entries;
+    }
+
+    if let Some(meshex) = meshex {
+        // Entry flags are stored in mesh object order for each mesh name.
+        // Renumbering doesn't change the object order, so only the flag count may change.
+        for group in &mut meshex.mesh_object_groups {
+            if let Some(count) = counts.get(&group.mesh_object_full_name) {
+                group.entry_flags.resize(
+                    *count as usize,
+                    EntryFlags {
+                        draw_model: true,
+                        cast_shadow: true,
+                    },
+                );
+            }
+        }
+    }
+}
+
 fn show_influences(ui: &mut Ui, mesh_object: &MeshObjectData) -> egui::InnerResponse<()> {
     // TODO: Add an option to show this per vertex instead of per bone?
     // Use a simple layout for now to avoid performance overhead of doing it per vertex.
@@ -464,10 +593,37 @@ fn edit_attribute_name(ui: &mut Ui, name: &mut String, id: egui::Id, valid_names
         });
 }
 
+const UV_NAMES: &[&str] = &["map1", "bake1", "uvSet", "uvSet1", "uvSet2"];
+
+const COLOR_SET_NAMES: &[&str] = &[
+    "colorSet1",
+    "colorSet2",
+    "colorSet2_1",
+    "colorSet2_2",
+    "colorSet2_3",
+    "colorSet3",
+    "colorSet4",
+    "colorSet5",
+    "colorSet6",
+    "colorSet7",
+];
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum AttributeAction {
+    Duplicate(usize),
+    Delete(usize),
+    MoveUp(usize),
+    MoveDown(usize),
+    Fill(usize),
+    CopyFrom { index: usize, source: usize },
+    FlipV(usize),
+}
+
 fn edit_mesh_attributes(
     ui: &mut Ui,
     mesh_object: &mut MeshObjectData,
     missing_attributes: &[String],
+    fill_value: &mut [f32; 4],
 ) -> bool {
     let mut changed = false;
 
@@ -484,11 +640,18 @@ fn edit_mesh_attributes(
         changed = true;
     }
 
+    // Apply actions after the grid to avoid modifying the lists while iterating.
+    let mut uv_action = None;
+    let mut color_set_action = None;
+
     egui::Grid::new("vertex_attributes_grid").show(ui, |ui| {
         // TODO: Create a size between heading and label?
         ui.label(RichText::new("Name").size(16.0));
         ui.label(RichText::new("Usage").size(16.0));
         ui.label(RichText::new("Vertex Count").size(16.0));
+        ui.label(RichText::new("Range").size(16.0));
+        // UV and color set rows have an additional menu column.
+        ui.label("");
         ui.end_row();
 
         // Vertex buffer 0.
@@ -497,66 +660,269 @@ fn edit_mesh_attributes(
             edit_attribute_name(ui, &mut a.name, id.with("pos").with(i), &["Position0"]);
             ui.label("Position");
             ui.label(a.data.len().to_string());
+            attribute_range_label(ui, &a.data);
             ui.end_row();
         }
         for (i, a) in mesh_object.normals.iter_mut().enumerate() {
             edit_attribute_name(ui, &mut a.name, id.with("nrm").with(i), &["Normal0"]);
             ui.label("Normal");
             ui.label(a.data.len().to_string());
+            attribute_range_label(ui, &a.data);
             ui.end_row();
         }
         for (i, a) in mesh_object.tangents.iter_mut().enumerate() {
             edit_attribute_name(ui, &mut a.name, id.with("tan").with(i), &["Tangent0"]);
             ui.label("Tangent");
             ui.label(a.data.len().to_string());
+            attribute_range_label(ui, &a.data);
             ui.end_row();
         }
         for (i, a) in mesh_object.binormals.iter_mut().enumerate() {
             edit_attribute_name(ui, &mut a.name, id.with("binrm").with(i), &["Binormal0"]);
             ui.label("Binormal (Bitangent)");
             ui.label(a.data.len().to_string());
+            attribute_range_label(ui, &a.data);
             ui.end_row();
         }
 
         // Vertex buffer 1.
+        let uv_names: Vec<_> = mesh_object
+            .texture_coordinates
+            .iter()
+            .map(|a| a.name.clone())
+            .collect();
         for (i, a) in mesh_object.texture_coordinates.iter_mut().enumerate() {
-            edit_attribute_name(
-                ui,
-                &mut a.name,
-                id.with("uv").with(i),
-                &["map1", "bake1", "uvSet", "uvSet1", "uvSet2"],
-            );
+            edit_attribute_name(ui, &mut a.name, id.with("uv").with(i), UV_NAMES);
             ui.label("Texture Coordinate (UV)");
             ui.label(a.data.len().to_string());
+            attribute_range_label(ui, &a.data);
+            if let Some(action) = attribute_menu(ui, i, &uv_names, fill_value, true) {
+                uv_action = Some(action);
+            }
             ui.end_row();
         }
+
+        let color_set_names: Vec<_> = mesh_object
+            .color_sets
+            .iter()
+            .map(|a| a.name.clone())
+            .collect();
         for (i, a) in mesh_object.color_sets.iter_mut().enumerate() {
-            edit_attribute_name(
-                ui,
-                &mut a.name,
-                id.with("color").with(i),
-                &[
-                    "colorSet1",
-                    "colorSet2",
-                    "colorSet2_1",
-                    "colorSet2_2",
-                    "colorSet2_3",
-                    "colorSet3",
-                    "colorSet4",
-                    "colorSet5",
-                    "colorSet6",
-                    "colorSet7",
-                ],
-            );
+            edit_attribute_name(ui, &mut a.name, id.with("color").with(i), COLOR_SET_NAMES);
             ui.label("Color Set (Vertex Color)");
             ui.label(a.data.len().to_string());
+            attribute_range_label(ui, &a.data);
+            if let Some(action) = attribute_menu(ui, i, &color_set_names, fill_value, false) {
+                color_set_action = Some(action);
+            }
             ui.end_row();
         }
     });
 
+    if let Some(action) = uv_action {
+        apply_attribute_action(
+            &mut mesh_object.texture_coordinates,
+            action,
+            *fill_value,
+            UV_NAMES,
+        );
+        changed = true;
+    }
+    if let Some(action) = color_set_action {
+        apply_attribute_action(
+            &mut mesh_object.color_sets,
+            action,
+            *fill_value,
+            COLOR_SET_NAMES,
+        );
+        changed = true;
+    }
+
     changed
 }
 
+fn attribute_menu(
+    ui: &mut Ui,
+    i: usize,
+    names: &[String],
+    fill_value: &mut [f32; 4],
+    is_uv: bool,
+) -> Option<AttributeAction> {
+    let mut action = None;
+    ui.menu_button("...", |ui| {
+        if ui.button("Duplicate").clicked() {
+            action = Some(AttributeAction::Duplicate(i));
+        }
+        if ui.button("Delete").clicked() {
+            action = Some(AttributeAction::Delete(i));
+        }
+        ui.separator();
+
+        if ui.add_enabled(i > 0, Button::new("Move Up")).clicked() {
+            action = Some(AttributeAction::MoveUp(i));
+        }
+        if ui
+            .add_enabled(i + 1 < names.len(), Button::new("Move Down"))
+            .clicked()
+        {
+            action = Some(AttributeAction::MoveDown(i));
+        }
+        ui.separator();
+
+        ui.menu_button("Copy From", |ui| {
+            for (source, name) in names.iter().enumerate() {
+                if source != i && ui.button(name).clicked() {
+                    action = Some(AttributeAction::CopyFrom { index: i, source });
+                }
+            }
+        });
+
+        ui.menu_button("Fill With Constant", |ui| {
+            // UVs only use the first two components.
+            let component_count = if is_uv { 2 } else { 4 };
+            ui.horizontal(|ui| {
+                for value in &mut fill_value[..component_count] {
+                    ui.add(egui::DragValue::new(value).speed(0.01));
+                }
+            });
+            if ui.button("Fill").clicked() {
+                action = Some(AttributeAction::Fill(i));
+            }
+        });
+
+        if is_uv
+            && ui
+                .button("Flip V")
+                .on_hover_text("Flip the UVs vertically by replacing V with 1.0 - V.")
+                .clicked()
+        {
+            action = Some(AttributeAction::FlipV(i));
+        }
+    });
+    action
+}
+
+fn apply_attribute_action(
+    attributes: &mut Vec<AttributeData>,
+    action: AttributeAction,
+    fill_value: [f32; 4],
+    valid_names: &[&str],
+) {
+    match action {
+        AttributeAction::Duplicate(i) => {
+            if let Some(a) = attributes.get(i) {
+                // Pick an unused name if possible to avoid duplicate attribute names.
+                let name = valid_names
+                    .iter()
+                    .find(|n| !attributes.iter().any(|a| a.name == **n))
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(|| a.name.clone());
+                let new_attribute = AttributeData {
+                    name,
+                    data: a.data.clone(),
+                };
+                attributes.insert(i + 1, new_attribute);
+            }
+        }
+        AttributeAction::Delete(i) => {
+            if i < attributes.len() {
+                attributes.remove(i);
+            }
+        }
+        AttributeAction::MoveUp(i) => {
+            if i > 0 && i < attributes.len() {
+                attributes.swap(i - 1, i);
+            }
+        }
+        AttributeAction::MoveDown(i) => {
+            if i + 1 < attributes.len() {
+                attributes.swap(i, i + 1);
+            }
+        }
+        AttributeAction::Fill(i) => {
+            if let Some(a) = attributes.get_mut(i) {
+                a.data = filled_data(&a.data, fill_value);
+            }
+        }
+        AttributeAction::CopyFrom { index, source } => {
+            if let Some(data) = attributes.get(source).map(|a| a.data.clone())
+                && let Some(a) = attributes.get_mut(index)
+            {
+                a.data = data;
+            }
+        }
+        AttributeAction::FlipV(i) => {
+            if let Some(a) = attributes.get_mut(i) {
+                flip_v(&mut a.data);
+            }
+        }
+    }
+}
+
+fn filled_data(data: &VectorData, value: [f32; 4]) -> VectorData {
+    // Preserve the component count and vertex count of the original data.
+    match data {
+        VectorData::Vector2(v) => VectorData::Vector2(vec![[value[0], value[1]]; v.len()]),
+        VectorData::Vector3(v) => {
+            VectorData::Vector3(vec![[value[0], value[1], value[2]]; v.len()])
+        }
+        VectorData::Vector4(v) => VectorData::Vector4(vec![value; v.len()]),
+    }
+}
+
+fn flip_v(data: &mut VectorData) {
+    match data {
+        VectorData::Vector2(values) => values.iter_mut().for_each(|v| v[1] = 1.0 - v[1]),
+        VectorData::Vector3(values) => values.iter_mut().for_each(|v| v[1] = 1.0 - v[1]),
+        VectorData::Vector4(values) => values.iter_mut().for_each(|v| v[1] = 1.0 - v[1]),
+    }
+}
+
+fn attribute_range_label(ui: &mut Ui, data: &VectorData) {
+    let (min, max, has_invalid_values) = match data {
+        VectorData::Vector2(v) => component_range(v),
+        VectorData::Vector3(v) => component_range(v),
+        VectorData::Vector4(v) => component_range(v),
+    };
+
+    let text = format!("{} to {}", format_components(&min), format_components(&max));
+    if has_invalid_values {
+        ui.label(warning_icon_text(&text))
+            .on_hover_text("The attribute contains NaN or infinite values.");
+    } else {
+        ui.label(text);
+    }
+}
+
+fn component_range<const N: usize>(values: &[[f32; N]]) -> (Vec<f32>, Vec<f32>, bool) {
+    let mut min = vec![0.0f32; N];
+    let mut max = vec![0.0f32; N];
+    let mut has_invalid_values = false;
+
+    if let Some(first) = values.iter().find(|v| v.iter().all(|f| f.is_finite())) {
+        min.copy_from_slice(first);
+        max.copy_from_slice(first);
+    }
+
+    for v in values {
+        for (c, f) in v.iter().enumerate() {
+            if f.is_finite() {
+                min[c] = min[c].min(*f);
+                max[c] = max[c].max(*f);
+            } else {
+                has_invalid_values = true;
+            }
+        }
+    }
+
+    (min, max, has_invalid_values)
+}
+
+fn format_components(values: &[f32]) -> String {
+    let components: Vec<_> = values.iter().map(|v| format!("{v:.3}")).collect();
+    format!("({})", components.join(", "))
+}
+
 fn add_uv(mesh_object: &mut MeshObjectData, name: &str, count: usize) {
     mesh_object.texture_coordinates.push(AttributeData {
         name: name.to_owned(),
@@ -603,6 +969,8 @@ fn add_missing_attributes(mesh_object: &mut MeshObjectData, missing_attributes:
 
 #[cfg(test)]
 mod tests {
+    use ssbh_data::meshex_data::MeshObjectGroupData;
+
     use super::*;
 
     #[test]
@@ -692,4 +1060,166 @@ mod tests {
         assert_eq!("a", mesh.objects[2].name);
         assert_eq!(0, mesh.objects[2].subindex);
     }
+
+    #[test]
+    fn duplicate_attribute_unused_name() {
+        let mut attributes = vec![AttributeData {
+            name: "map1".to_owned(),
+            data: VectorData::Vector2(vec![[0.25, 0.5]]),
+        }];
+
+        apply_attribute_action(
+            &mut attributes,
+            AttributeAction::Duplicate(0),
+            [0.0; 4],
+            UV_NAMES,
+        );
+
+        assert_eq!("map1", attributes[0].name);
+        assert_eq!("bake1", attributes[1].name);
+        assert_eq!(VectorData::Vector2(vec![[0.25, 0.5]]), attributes[1].data);
+    }
+
+    #[test]
+    fn copy_fill_flip_attributes() {
+        let mut attributes = vec![
+            AttributeData {
+                name: "map1".to_owned(),
+                data: VectorData::Vector2(vec![[0.25, 0.25], [0.5, 1.0]]),
+            },
+            AttributeData {
+                name: "bake1".to_owned(),
+                data: VectorData::Vector2(vec![[0.0, 0.0], [0.0, 0.0]]),
+            },
+        ];
+
+        apply_attribute_action(
+            &mut attributes,
+            AttributeAction::CopyFrom {
+                index: 1,
+                source: 0,
+            },
+            [0.0; 4],
+            UV_NAMES,
+        );
+        assert_eq!(attributes[0].data, attributes[1].data);
+
+        apply_attribute_action(
+            &mut attributes,
+            AttributeAction::FlipV(1),
+            [0.0; 4],
+            UV_NAMES,
+        );
+        assert_eq!(
+            VectorData::Vector2(vec![[0.25, 0.75], [0.5, 0.0]]),
+            attributes[1].data
+        );
+
+        apply_attribute_action(
+            &mut attributes,
+            AttributeAction::Fill(0),
+            [1.0, 2.0, 3.0, 4.0],
+            UV_NAMES,
+        );
+        assert_eq!(
+            VectorData::Vector2(vec![[1.0, 2.0], [1.0, 2.0]]),
+            attributes[0].data
+        );
+
+        apply_attribute_action(
+            &mut attributes,
+            AttributeAction::MoveDown(0),
+            [0.0; 4],
+            UV_NAMES,
+        );
+        assert_eq!("bake1", attributes[0].name);
+        assert_eq!("map1", attributes[1].name);
+
+        apply_attribute_action(
+            &mut attributes,
+            AttributeAction::Delete(0),
+            [0.0; 4],
+            UV_NAMES,
+        );
+        assert_eq!(1, attributes.len());
+        assert_eq!("map1", attributes[0].name);
+    }
+
+    #[test]
+    fn component_range_invalid_values() {
+        let (min, max, has_invalid_values) =
+            component_range(&[[f32::NAN, 0.5], [-1.0, 2.0], [0.5, f32::INFINITY]]);
+        assert_eq!(vec![-1.0, 0.5], min);
+        assert_eq!(vec![0.5, 2.0], max);
+        assert!(has_invalid_values);
+    }
+
+    #[test]
+    fn auto_number_subindices_update_modl_meshex() {
+        let mesh_object = |name: &str, subindex| MeshObjectData {
+            name: name.to_owned(),
+            subindex,
+            ..Default::default()
+        };
+        let mut mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![
+                mesh_object("a", 0),
+                mesh_object("b", 0),
+                mesh_object("a", 0),
+                mesh_object("a", 1),
+            ],
+        };
+
+        let entry = |name: &str, subindex, material: &str| ModlEntryData {
+            mesh_object_name: name.to_owned(),
+            mesh_object_subindex: subindex,
+            material_label: material.to_owned(),
+        };
+        let mut modl = ModlData {
+            major_version: 1,
+            minor_version: 0,
+            model_name: String::new(),
+            skeleton_file_name: String::new(),
+            material_file_names: Vec::new(),
+            animation_file_name: None,
+            mesh_file_name: String::new(),
+            entries: vec![entry("a", 0, "x"), entry("a", 1, "y"), entry("b", 0, "z")],
+        };
+
+        let flags = |draw_model| EntryFlags {
+            draw_model,
+            cast_shadow: true,
+        };
+        let mut meshex = MeshExData {
+            mesh_object_groups: vec![MeshObjectGroupData {
+                bounding_sphere: ssbh_data::Vector4::new(0.0, 0.0, 0.0, 0.0),
+                mesh_object_full_name: "a".to_owned(),
+                mesh_object_name: "a".to_owned(),
+                // Duplicate subindices should keep the flags for each mesh object.
+                entry_flags: vec![flags(true), flags(false)],
+            }],
+        };
+
+        auto_number_subindices(&mut mesh, Some(&mut modl), Some(&mut meshex));
+
+        let subindices: Vec<_> = mesh.objects.iter().map(|o| o.subindex).collect();
+        assert_eq!(vec![0, 0, 1, 2], subindices);
+
+        assert_eq!(
+            vec![
+                entry("a", 0, "x"),
+                entry("a", 1, "x"),
+                entry("a", 2, "y"),
+                entry("b", 0, "z")
+            ],
+            modl.entries
+        );
+
+        assert_eq!(
+            vec![flags(true), flags(false), flags(true)],
+            meshex.mesh_object_groups[0].entry_flags
+        );
+    }
 }
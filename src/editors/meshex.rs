@@ -1,4 +1,11 @@
-use crate::{EditorMessage, EditorResponse, path::folder_editor_title, save_file, save_file_as};
+use crate::{
+    EditorMessage, EditorResponse,
+    app::{display_validation_errors, warning_icon_text},
+    bounds::{BoundingSphere, recompute_meshex_bounds},
+    path::folder_editor_title,
+    save_file, save_file_as,
+    validation::MeshExValidationError,
+};
 use egui::{Grid, Label, Response, ScrollArea, Sense, Ui, special_emojis::GITHUB};
 
 use ssbh_data::prelude::*;
@@ -10,6 +17,7 @@ pub fn meshex_editor(
     file_name: &str,
     meshex: &mut MeshExData,
     mesh: Option<&MeshData>,
+    validation_errors: &[MeshExValidationError],
 ) -> EditorResponse {
     let mut open = true;
     let mut changed = false;
@@ -43,6 +51,16 @@ pub fn meshex_editor(
                         *meshex = MeshExData::from_mesh_objects(&mesh.objects);
                         changed = true;
                     }
+
+                    if ui
+                        .add_enabled(mesh.is_some(), egui::Button::new("Recompute Bounds"))
+                        .on_hover_text("Recalculate the bounding spheres while preserving flags.")
+                        .clicked()
+                        && let Some(mesh) = mesh
+                    {
+                        recompute_meshex_bounds(meshex, mesh);
+                        changed = true;
+                    }
                 });
 
                 ui.menu_button("Help", |ui| {
@@ -57,23 +75,45 @@ pub fn meshex_editor(
             });
             ui.separator();
 
+            display_validation_errors(ui, validation_errors.iter());
+
             ScrollArea::vertical()
                 .auto_shrink([false; 2])
                 .show(ui, |ui| {
-                    // TODO: Show bounding sphere values?
-                    Grid::new("meshex_grid").num_columns(4).show(ui, |ui| {
+                    Grid::new("meshex_grid").num_columns(5).show(ui, |ui| {
                         ui.heading("Full Name");
                         ui.heading("Name");
+                        ui.heading("Bounding Sphere");
                         ui.label("");
                         ui.label("");
                         ui.end_row();
 
-                        for group in &mut meshex.mesh_object_groups {
+                        for (group_index, group) in meshex.mesh_object_groups.iter_mut().enumerate()
+                        {
+                            let has_bounds_error = validation_errors.iter().any(|e| {
+                                matches!(
+                                    e,
+                                    MeshExValidationError::BoundingSphereMissingVertices {
+                                        group_index: i,
+                                        ..
+                                    } if *i == group_index
+                                )
+                            });
+
                             for (subindex, entry) in group.entry_flags.iter_mut().enumerate() {
                                 // Get responses outside the if condition to always show both labels.
                                 let response1 = hoverable_label(ui, &group.mesh_object_full_name);
                                 let response2 = hoverable_label(ui, &group.mesh_object_name);
 
+                                // The bounding sphere is shared by all entries in the group.
+                                if subindex == 0 {
+                                    let sphere =
+                                        BoundingSphere::from_vector4(&group.bounding_sphere);
+                                    bounding_sphere_label(ui, &sphere, has_bounds_error);
+                                } else {
+                                    ui.label("");
+                                }
+
                                 // TODO: Return a message enum instead.
                                 if response1.hovered() || response2.hovered() {
                                     // Outline the selected mesh in the viewport.
@@ -102,6 +142,98 @@ pub fn meshex_editor(
     }
 }
 
+pub fn match_meshex_order_to_mesh(meshex: &mut MeshExData, mesh: &MeshData) {
+    meshex.mesh_object_groups.sort_by_key(|g| {
+        // The sort is stable, so unmatched groups will be placed at the end in the same order.
+        mesh.objects
+            .iter()
+            .position(|o| o.name == g.mesh_object_full_name)
+            .unwrap_or(mesh.objects.len())
+    })
+}
+
+fn bounding_sphere_label(ui: &mut Ui, sphere: &BoundingSphere, has_error: bool) {
+    let [x, y, z] = sphere.center;
+    let text = format!("({x:.3}, {y:.3}, {z:.3}) r={:.3}", sphere.radius);
+    if has_error {
+        ui.label(warning_icon_text(&text))
+            .on_hover_text("The bounding sphere does not contain all of the vertices.");
+    } else {
+        ui.label(text);
+    }
+}
+
 fn hoverable_label(ui: &mut Ui, label: &str) -> Response {
     ui.add(Label::new(label).sense(Sense::click()))
 }
+
+#[cfg(test)]
+mod tests {
+    use ssbh_data::{
+        Vector4,
+        mesh_data::MeshObjectData,
+        meshex_data::{EntryFlags, MeshObjectGroupData},
+    };
+
+    use super::*;
+
+    fn group(name: &str, entry_flags: Vec<EntryFlags>) -> MeshObjectGroupData {
+        MeshObjectGroupData {
+            bounding_sphere: Vector4::new(0.0, 0.0, 0.0, 0.0),
+            mesh_object_full_name: name.to_owned(),
+            mesh_object_name: name.to_owned(),
+            entry_flags,
+        }
+    }
+
+    fn flags(draw_model: bool, cast_shadow: bool) -> EntryFlags {
+        EntryFlags {
+            draw_model,
+            cast_shadow,
+        }
+    }
+
+    #[test]
+    fn meshex_order_to_mesh() {
+        let mut meshex = MeshExData {
+            mesh_object_groups: vec![
+                group("c", vec![flags(true, true)]),
+                group("a", vec![flags(false, true), flags(true, false)]),
+                group("b", vec![flags(true, false)]),
+            ],
+        };
+        let mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![
+                MeshObjectData {
+                    name: "b".to_owned(),
+                    subindex: 0,
+                    ..Default::default()
+                },
+                MeshObjectData {
+                    name: "a".to_owned(),
+                    subindex: 0,
+                    ..Default::default()
+                },
+                MeshObjectData {
+                    name: "a".to_owned(),
+                    subindex: 1,
+                    ..Default::default()
+                },
+            ],
+        };
+
+        match_meshex_order_to_mesh(&mut meshex, &mesh);
+
+        // Unmatched groups are placed at the end, and flags stay with their group.
+        assert_eq!(
+            vec![
+                group("b", vec![flags(true, false)]),
+                group("a", vec![flags(false, true), flags(true, false)]),
+                group("c", vec![flags(true, true)]),
+            ],
+            meshex.mesh_object_groups
+        );
+    }
+}
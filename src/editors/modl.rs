@@ -2,12 +2,21 @@ use crate::{
     EditorMessage, EditorResponse,
     app::{ModlEditorState, ModlEditorTab, icon::draggable_icon, warning_icon_text},
     horizontal_separator_empty,
+    modl::{
+        MaterialAssignmentRule, assign_materials, load_material_assignment_rules,
+        save_material_assignment_rules,
+    },
     path::folder_editor_title,
     save_file, save_file_as,
     validation::{ModlValidationError, ModlValidationErrorKind},
 };
-use egui::{Grid, Label, RichText, ScrollArea, TextEdit, special_emojis::GITHUB};
+use egui::{
+    Button, DragValue, Grid, Label, RichText, ScrollArea, TextEdit, TextWrapMode,
+    special_emojis::GITHUB,
+};
 use egui_dnd::dnd;
+use log::error;
+use rfd::FileDialog;
 
 use ssbh_data::{mesh_data::MeshObjectData, modl_data::ModlEntryData, prelude::*};
 use std::path::Path;
@@ -50,6 +59,24 @@ pub fn modl_editor(
                     {
                         changed |= rebuild_from_mesh(modl, mesh, matl);
                     }
+
+                    if ui
+                        .add(
+                            Button::new("Match Reference Order...").wrap_mode(TextWrapMode::Extend),
+                        )
+                        .clicked()
+                        && let Some(file) = FileDialog::new()
+                            .add_filter("Modl", &["numdlb"])
+                            .pick_file()
+                    {
+                        match ModlData::from_file(&file) {
+                            Ok(reference) => {
+                                match_modl_order(modl, &reference);
+                                changed = true;
+                            }
+                            Err(e) => error!("Failed to read {file:?}: {e}"),
+                        }
+                    }
                 });
 
                 ui.menu_button("Help", |ui| {
@@ -69,6 +96,11 @@ pub fn modl_editor(
                     ModlEditorTab::Assignments,
                     RichText::new("Materials").heading(),
                 );
+                ui.selectable_value(
+                    &mut state.editor_tab,
+                    ModlEditorTab::Rules,
+                    RichText::new("Rules").heading(),
+                );
                 ui.selectable_value(
                     &mut state.editor_tab,
                     ModlEditorTab::Files,
@@ -86,6 +118,9 @@ pub fn modl_editor(
                     dark_mode,
                     &mut message,
                 ),
+                ModlEditorTab::Rules => {
+                    edit_material_assignment_rules(ui, modl, matl, &mut state.material_rules)
+                }
                 ModlEditorTab::Files => edit_modl_file_names(ui, modl),
             }
         });
@@ -285,6 +320,30 @@ fn edit_modl_entries(
     changed
 }
 
+fn match_modl_order(modl: &mut ModlData, reference: &ModlData) {
+    modl.entries.sort_by_key(|e| {
+        // The sort is stable, so unmatched entries will be placed at the end in the same order.
+        reference
+            .entries
+            .iter()
+            .position(|r| {
+                r.mesh_object_name == e.mesh_object_name
+                    && r.mesh_object_subindex == e.mesh_object_subindex
+            })
+            .unwrap_or(reference.entries.len())
+    })
+}
+
+pub fn match_modl_order_to_mesh(modl: &mut ModlData, mesh: &MeshData) {
+    modl.entries.sort_by_key(|e| {
+        // The sort is stable, so unmatched entries will be placed at the end in the same order.
+        mesh.objects
+            .iter()
+            .position(|o| o.name == e.mesh_object_name && o.subindex == e.mesh_object_subindex)
+            .unwrap_or(mesh.objects.len())
+    })
+}
+
 fn has_unused_entries(modl: &ModlData, mesh: &MeshData) -> bool {
     modl.entries.iter().any(|e| {
         !mesh
@@ -306,6 +365,134 @@ fn missing_mesh_objects<'a>(modl: &ModlData, mesh: &'a MeshData) -> Vec<&'a Mesh
         .collect()
 }
 
+fn edit_material_assignment_rules(
+    ui: &mut egui::Ui,
+    modl: &mut ModlData,
+    matl: Option<&MatlData>,
+    rules: &mut Vec<MaterialAssignmentRule>,
+) -> bool {
+    let mut changed = false;
+
+    ui.label("Assign materials to mesh objects matching a pattern. * matches any characters, and ? matches a single character. The first matching rule is used for each mesh object.");
+    horizontal_separator_empty(ui);
+
+    let mut index_to_remove = None;
+    Grid::new("modl_rules_grid").show(ui, |ui| {
+        ui.heading("Pattern");
+        ui.heading("Subindex");
+        ui.heading("Material");
+        ui.end_row();
+
+        for (i, rule) in rules.iter_mut().enumerate() {
+            let id = egui::Id::new("modl_rule").with(i);
+
+            ui.add(TextEdit::singleline(&mut rule.pattern).desired_width(200.0));
+
+            ui.horizontal(|ui| {
+                let mut has_subindex = rule.subindex.is_some();
+                if ui.checkbox(&mut has_subindex, "").changed() {
+                    rule.subindex = has_subindex.then_some(0);
+                }
+                if let Some(subindex) = &mut rule.subindex {
+                    ui.add(DragValue::new(subindex));
+                }
+            });
+
+            let is_valid = matl.is_some_and(|m| {
+                m.entries
+                    .iter()
+                    .any(|e| e.material_label == rule.material_label)
+            });
+            material_label_combo_box(ui, &mut rule.material_label, id, matl, is_valid);
+
+            if ui.button("Remove").clicked() {
+                index_to_remove = Some(i);
+            }
+            ui.end_row();
+        }
+    });
+
+    if let Some(i) = index_to_remove {
+        rules.remove(i);
+    }
+
+    ui.horizontal(|ui| {
+        if ui.button("Add Rule").clicked() {
+            rules.push(MaterialAssignmentRule {
+                pattern: String::new(),
+                subindex: None,
+                material_label: matl
+                    .and_then(|m| m.entries.first().map(|e| e.material_label.clone()))
+                    .unwrap_or_default(),
+            });
+        }
+
+        if ui.button("Load Rules...").clicked()
+            && let Some(file) = FileDialog::new()
+                .add_filter("Material Assignment Rules", &["json"])
+                .pick_file()
+            && let Some(new_rules) = load_material_assignment_rules(file)
+        {
+            *rules = new_rules;
+        }
+
+        if ui.button("Save Rules...").clicked()
+            && let Some(file) = FileDialog::new()
+                .add_filter("Material Assignment Rules", &["json"])
+                .save_file()
+        {
+            save_material_assignment_rules(rules, file);
+        }
+    });
+    horizontal_separator_empty(ui);
+
+    let assignments = assign_materials(&modl.entries, rules);
+    if ui
+        .add_enabled(
+            !assignments.is_empty(),
+            Button::new(format!("Apply {} Changes", assignments.len())),
+        )
+        .clicked()
+    {
+        for assignment in &assignments {
+            modl.entries[assignment.entry_index]
+                .material_label
+                .clone_from(&assignment.new_material);
+        }
+        changed = true;
+    }
+
+    if assignments.is_empty() {
+        ui.label("No material changes found.");
+    }
+
+    ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show(ui, |ui| {
+            Grid::new("modl_rules_preview_grid")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.heading("Mesh Object");
+                    ui.heading("Current");
+                    ui.heading("New");
+                    ui.end_row();
+
+                    for assignment in &assignments {
+                        let entry = &modl.entries[assignment.entry_index];
+                        ui.label(format!(
+                            "{} ({})",
+                            entry.mesh_object_name, entry.mesh_object_subindex
+                        ));
+                        ui.label(&assignment.old_material);
+                        ui.label(&assignment.new_material);
+                        ui.end_row();
+                    }
+                });
+        });
+
+    changed
+}
+
 fn edit_modl_file_names(ui: &mut egui::Ui, modl: &mut ModlData) -> bool {
     let mut changed = false;
 
@@ -386,3 +573,75 @@ fn material_label_combo_box(
         });
     changed
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modl_data(entries: Vec<ModlEntryData>) -> ModlData {
+        ModlData {
+            major_version: 1,
+            minor_version: 0,
+            model_name: String::new(),
+            skeleton_file_name: String::new(),
+            material_file_names: Vec::new(),
+            animation_file_name: None,
+            mesh_file_name: String::new(),
+            entries,
+        }
+    }
+
+    fn entry(name: &str, subindex: u64) -> ModlEntryData {
+        ModlEntryData {
+            mesh_object_name: name.to_owned(),
+            mesh_object_subindex: subindex,
+            material_label: String::new(),
+        }
+    }
+
+    #[test]
+    fn modl_order_added_entries() {
+        let mut modl = modl_data(vec![
+            entry("a", 1),
+            entry("c", 0),
+            entry("a", 0),
+            entry("b", 0),
+        ]);
+        let reference = modl_data(vec![entry("b", 0), entry("a", 0), entry("a", 1)]);
+
+        match_modl_order(&mut modl, &reference);
+
+        assert_eq!(
+            vec![entry("b", 0), entry("a", 0), entry("a", 1), entry("c", 0)],
+            modl.entries
+        );
+    }
+
+    #[test]
+    fn modl_order_to_mesh() {
+        let mut modl = modl_data(vec![entry("c", 0), entry("a", 0), entry("b", 0)]);
+        let mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![
+                MeshObjectData {
+                    name: "b".to_owned(),
+                    subindex: 0,
+                    ..Default::default()
+                },
+                MeshObjectData {
+                    name: "a".to_owned(),
+                    subindex: 0,
+                    ..Default::default()
+                },
+            ],
+        };
+
+        match_modl_order_to_mesh(&mut modl, &mesh);
+
+        assert_eq!(
+            vec![entry("b", 0), entry("a", 0), entry("c", 0)],
+            modl.entries
+        );
+    }
+}
@@ -1,22 +1,86 @@
-use crate::{RenderState, app::NutexbViewerState, horizontal_separator_empty};
-use egui::{ComboBox, DragValue, Scene, Slider, special_emojis::GITHUB};
+use crate::{
+    EditorResponse, RenderState,
+    app::{NutexbViewerState, display_validation_errors},
+    horizontal_separator_empty, save_file, save_file_as,
+    texture::{
+        ChannelStatistics, EXPORT_EXTENSIONS, IMPORT_FORMATS, TextureReference, channel_warnings,
+        convert_nutexb, export_nutexb, nutexb_channel_statistics, nutexb_name, srgb_format,
+        texture_references,
+    },
+    validation::{NutexbValidationError, expects_srgb},
+};
+use egui::{CollapsingHeader, Color32, ComboBox, DragValue, Scene, Slider, special_emojis::GITHUB};
+use egui_plot::{Legend, Line, Plot};
 use egui_wgpu::{Callback, CallbackTrait};
 use nutexb::{NutexbFile, NutexbFormat};
 use nutexb_wgpu::RenderSettings;
+use rfd::FileDialog;
+use ssbh_data::prelude::*;
+use std::path::Path;
 
+#[allow(clippy::too_many_arguments)]
 pub fn nutexb_viewer(
     ctx: &egui::Context,
     title: &str,
+    folder_name: &Path,
+    file_name: &str,
     state: &mut NutexbViewerState,
-    nutexb: &NutexbFile,
+    nutexb: &mut NutexbFile,
+    validation_errors: &[NutexbValidationError],
+    matls: &[(String, Option<MatlData>)],
     settings: &mut RenderSettings,
-) -> bool {
+) -> EditorResponse {
     let mut open = true;
+    let mut changed = false;
+    let mut saved = false;
+
     egui::Window::new(format!("Nutexb Viewer ({title})"))
         .open(&mut open)
         .default_size((500.0, 600.0))
         .show(ctx, |ui| {
             egui::MenuBar::new().ui(ui, |ui| {
+                ui.menu_button("File", |ui| {
+                    if ui.button("Save").clicked() {
+                        saved |= save_file(nutexb, folder_name, file_name);
+                    }
+
+                    if ui.button("Save As...").clicked() {
+                        saved |=
+                            save_file_as(nutexb, folder_name, file_name, "Nutexb", "nutexb");
+                    }
+
+                    ui.separator();
+
+                    if ui.button("Export As...").clicked()
+                        && let Some(file) = FileDialog::new()
+                            .set_directory(folder_name)
+                            .set_file_name(
+                                Path::new(file_name).with_extension("png").to_string_lossy(),
+                            )
+                            .add_filter("Image", &EXPORT_EXTENSIONS)
+                            .save_file()
+                        && let Err(e) = export_nutexb(nutexb, &file)
+                    {
+                        log::error!("Failed to export {file:?}: {e}");
+                    }
+                });
+
+                ui.menu_button("Format", |ui| {
+                    ui.menu_button("Convert To", |ui| {
+                        for format in IMPORT_FORMATS {
+                            if ui
+                                .add_enabled(
+                                    format != nutexb.footer.image_format,
+                                    egui::Button::new(format_name(format)),
+                                )
+                                .clicked()
+                            {
+                                changed |= convert_format(nutexb, format);
+                            }
+                        }
+                    });
+                });
+
                 ui.menu_button("Help", |ui| {
                     if ui.button(format!("{GITHUB} Nutexb Editor Wiki")).clicked() {
                         let link =
@@ -54,12 +118,33 @@ pub fn nutexb_viewer(
                 ui.label(nutexb.footer.layer_count.to_string());
                 ui.end_row();
 
-                // TODO: Show an error if this doesn't match the actual data?
-                // TODO: Show an error if this doesn't match the expected size?
                 ui.label("Data Size");
                 ui.label(nutexb.footer.data_size.to_string());
                 ui.end_row();
             });
+
+            if !validation_errors.is_empty() {
+                horizontal_separator_empty(ui);
+                display_validation_errors(ui, validation_errors.iter());
+
+                for format in srgb_quick_fix_formats(validation_errors) {
+                    if ui
+                        .button(format!("Convert to {}", format_name(format)))
+                        .on_hover_text(
+                            "Switch to the sRGB or linear format expected by the assigned texture parameters.",
+                        )
+                        .clicked()
+                    {
+                        changed |= convert_format(nutexb, format);
+                    }
+                }
+            }
+            horizontal_separator_empty(ui);
+
+            let references = texture_references(matls, &nutexb_name(file_name));
+
+            ui.heading("Usage");
+            texture_usage(ui, &references);
             horizontal_separator_empty(ui);
 
             ui.heading("Image Data");
@@ -112,6 +197,11 @@ pub fn nutexb_viewer(
                 }
             });
 
+            CollapsingHeader::new("Channel Statistics").show(ui, |ui| {
+                let key = (title.to_owned(), settings.layer, settings.mipmap as u32);
+                channel_statistics(ui, state, key, nutexb, &references, settings);
+            });
+
             // TODO: Show a pixel grid in screen space?
             // TODO: Composite with a background color for alpha?
             // TODO: show controls for pan+zoom?
@@ -151,7 +241,138 @@ pub fn nutexb_viewer(
                 });
         });
 
-    open
+    if changed {
+        // Converted image data invalidates any cached statistics.
+        state.channel_statistics = None;
+    }
+
+    EditorResponse {
+        open,
+        changed,
+        saved,
+        message: None,
+    }
+}
+
+fn texture_usage(ui: &mut egui::Ui, references: &[TextureReference]) {
+    if references.is_empty() {
+        ui.label("This texture is not assigned to any materials.");
+    } else {
+        egui::Grid::new("nutexb_usage")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Matl");
+                ui.label("Material");
+                ui.label("Parameter");
+                ui.end_row();
+
+                for reference in references {
+                    ui.label(&reference.matl);
+                    ui.label(&reference.material_label);
+                    ui.label(reference.param_id.to_string());
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+fn channel_statistics(
+    ui: &mut egui::Ui,
+    state: &mut NutexbViewerState,
+    key: (String, u32, u32),
+    nutexb: &NutexbFile,
+    references: &[TextureReference],
+    settings: &RenderSettings,
+) {
+    // Decoding is expensive, so only update the statistics when the selection changes.
+    if state.channel_statistics.as_ref().map(|(k, _)| k) != Some(&key) {
+        let statistics = match nutexb_channel_statistics(nutexb, key.1, key.2) {
+            Ok(statistics) => Some(statistics),
+            Err(e) => {
+                log::error!("Failed to calculate channel statistics for {}: {e}", key.0);
+                None
+            }
+        };
+        state.channel_statistics = Some((key, statistics));
+    }
+
+    let Some((_, Some(statistics))) = &state.channel_statistics else {
+        ui.label("Failed to decode the image data. Check the application log for details.");
+        return;
+    };
+
+    egui::Grid::new("nutexb_channel_statistics").show(ui, |ui| {
+        ui.label("Channel");
+        ui.label("Min");
+        ui.label("Max");
+        ui.label("Mean");
+        ui.end_row();
+
+        for (i, channel) in ["R", "G", "B", "A"].into_iter().enumerate() {
+            ui.label(channel);
+            ui.label(format!("{:.3}", statistics.min[i]));
+            ui.label(format!("{:.3}", statistics.max[i]));
+            ui.label(format!("{:.3}", statistics.mean[i]));
+            ui.end_row();
+        }
+    });
+
+    channel_histogram(ui, statistics, settings);
+
+    let param_ids: Vec<_> = references.iter().map(|r| r.param_id).collect();
+    let warnings = channel_warnings(statistics, &param_ids);
+    display_validation_errors(ui, warnings.iter());
+}
+
+fn channel_histogram(ui: &mut egui::Ui, statistics: &ChannelStatistics, settings: &RenderSettings) {
+    let channels = [
+        ("R", Color32::RED),
+        ("G", Color32::GREEN),
+        ("B", Color32::BLUE),
+        ("A", Color32::GRAY),
+    ];
+
+    Plot::new("nutexb_histogram")
+        .height(150.0)
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .legend(Legend::default().follow_insertion_order(true))
+        .show(ui, |plot_ui| {
+            // Only show the channels currently displayed in the viewer.
+            for (i, (name, color)) in channels.into_iter().enumerate() {
+                if settings.render_rgba[i] {
+                    let points: Vec<_> = statistics.histogram[i]
+                        .iter()
+                        .enumerate()
+                        .map(|(value, count)| [value as f64 / 255.0, *count as f64])
+                        .collect();
+                    plot_ui.line(Line::new(name, points).color(color));
+                }
+            }
+        });
+}
+
+fn convert_format(nutexb: &mut NutexbFile, format: NutexbFormat) -> bool {
+    if let Err(e) = convert_nutexb(nutexb, format) {
+        log::error!("Failed to convert to {}: {e}", format_name(format));
+        false
+    } else {
+        true
+    }
+}
+
+fn srgb_quick_fix_formats(validation_errors: &[NutexbValidationError]) -> Vec<NutexbFormat> {
+    let mut formats = Vec::new();
+    for e in validation_errors {
+        if let NutexbValidationError::FormatInvalidForUsage { format, param, .. } = e {
+            let format = srgb_format(*format, expects_srgb(*param));
+            if !formats.contains(&format) {
+                formats.push(format);
+            }
+        }
+    }
+    formats
 }
 
 struct PaintTextureCallback;
@@ -169,7 +390,7 @@ impl CallbackTrait for PaintTextureCallback {
     }
 }
 
-fn format_name(format: NutexbFormat) -> &'static str {
+pub fn format_name(format: NutexbFormat) -> &'static str {
     match format {
         NutexbFormat::R8Unorm => "R8Unorm",
         NutexbFormat::R8G8B8A8Unorm => "R8G8B8A8Unorm",
@@ -0,0 +1,164 @@
+use crate::{
+    EditorResponse,
+    path::folder_editor_title,
+    swing::{
+        CAPSULE_FIELDS, FloatField, OVAL_FIELDS, PARAM_FIELDS, PLANE_FIELDS, SPHERE_FIELDS,
+        save_swing_prc,
+    },
+};
+use egui::{CollapsingHeader, DragValue, Grid, Id, ScrollArea, Ui};
+use log::error;
+use rfd::FileDialog;
+use ssbh_wgpu::swing::SwingPrc;
+use std::path::Path;
+
+pub fn swing_editor(
+    ctx: &egui::Context,
+    folder_name: &Path,
+    swing_prc: &mut SwingPrc,
+) -> EditorResponse {
+    let mut open = true;
+    let mut changed = false;
+    let mut saved = false;
+
+    let source_path = folder_name.join("swing.prc");
+
+    let title = folder_editor_title(folder_name, "swing.prc");
+    egui::Window::new(format!("Swing Editor ({title})"))
+        .open(&mut open)
+        .resizable(true)
+        .show(ctx, |ui| {
+            egui::MenuBar::new().ui(ui, |ui| {
+                ui.menu_button("File", |ui| {
+                    if ui.button("Save").clicked() {
+                        saved |= save_swing(swing_prc, &source_path, &source_path);
+                    }
+
+                    if ui.button("Save As...").clicked()
+                        && let Some(file) = FileDialog::new()
+                            .set_directory(folder_name)
+                            .set_file_name("swing.prc")
+                            .add_filter("Prc", &["prc"])
+                            .save_file()
+                    {
+                        saved |= save_swing(swing_prc, &source_path, &file);
+                    }
+                });
+            });
+            ui.separator();
+
+            ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    let id = Id::new("swing_editor");
+
+                    CollapsingHeader::new("Swing Bones")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            for (i, bone) in swing_prc.swingbones.iter_mut().enumerate() {
+                                let id = id.with("swingbones").with(i);
+                                CollapsingHeader::new(format!("swingbones[{i}] {}", bone.name))
+                                    .id_salt(id)
+                                    .show(ui, |ui| {
+                                        for (j, param) in bone.params.iter_mut().enumerate() {
+                                            CollapsingHeader::new(format!("params[{j}]"))
+                                                .id_salt(id.with(j))
+                                                .show(ui, |ui| {
+                                                    changed |= edit_fields(
+                                                        ui,
+                                                        id.with(j),
+                                                        param,
+                                                        &PARAM_FIELDS,
+                                                    );
+                                                });
+                                        }
+                                    });
+                            }
+                        });
+
+                    // Names are hashes shared with the collisions for each param.
+                    changed |= edit_shapes(
+                        ui,
+                        id,
+                        "Spheres",
+                        &mut swing_prc.spheres,
+                        &SPHERE_FIELDS,
+                        |s| s.name.to_string(),
+                    );
+                    changed |=
+                        edit_shapes(ui, id, "Ovals", &mut swing_prc.ovals, &OVAL_FIELDS, |o| {
+                            o.name.to_string()
+                        });
+                    changed |= edit_shapes(
+                        ui,
+                        id,
+                        "Capsules",
+                        &mut swing_prc.capsules,
+                        &CAPSULE_FIELDS,
+                        |c| c.name.to_string(),
+                    );
+                    changed |= edit_shapes(
+                        ui,
+                        id,
+                        "Planes",
+                        &mut swing_prc.planes,
+                        &PLANE_FIELDS,
+                        |p| p.name.to_string(),
+                    );
+                });
+        });
+
+    EditorResponse {
+        open,
+        changed,
+        saved,
+        message: None,
+    }
+}
+
+fn edit_shapes<T>(
+    ui: &mut Ui,
+    id: Id,
+    label: &str,
+    shapes: &mut [T],
+    fields: &[FloatField<T>],
+    name: impl Fn(&T) -> String,
+) -> bool {
+    let mut changed = false;
+    CollapsingHeader::new(format!("{label} ({})", shapes.len()))
+        .id_salt(id.with(label))
+        .show(ui, |ui| {
+            for (i, shape) in shapes.iter_mut().enumerate() {
+                let id = id.with(label).with(i);
+                CollapsingHeader::new(name(shape))
+                    .id_salt(id)
+                    .show(ui, |ui| {
+                        changed |= edit_fields(ui, id, shape, fields);
+                    });
+            }
+        });
+    changed
+}
+
+fn edit_fields<T>(ui: &mut Ui, id: Id, value: &mut T, fields: &[FloatField<T>]) -> bool {
+    let mut changed = false;
+    Grid::new(id.with("fields")).show(ui, |ui| {
+        for field in fields {
+            ui.label(field.name);
+            changed |= ui
+                .add(DragValue::new((field.get_mut)(value)).speed(0.01))
+                .changed();
+            ui.end_row();
+        }
+    });
+    changed
+}
+
+fn save_swing(swing_prc: &SwingPrc, source_path: &Path, path: &Path) -> bool {
+    if let Err(e) = save_swing_prc(swing_prc, source_path, path) {
+        error!("Failed to save {path:?}: {e}");
+        false
+    } else {
+        true
+    }
+}
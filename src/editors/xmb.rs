@@ -0,0 +1,313 @@
+use crate::{
+    EditorResponse,
+    app::{display_validation_errors, warning_icon_text},
+    path::folder_editor_title,
+    save_file, save_file_as,
+    validation::{XmbValidationError, is_numdlb_file_name},
+};
+use egui::{CollapsingHeader, ComboBox, Grid, Id, RichText, ScrollArea, TextEdit, Ui};
+use std::path::Path;
+use xmb_lib::{XmbEntry, XmbFile};
+
+pub fn xmb_editor(
+    ctx: &egui::Context,
+    folder_name: &Path,
+    file_name: &str,
+    xmb: &mut XmbFile,
+    numdlb_names: &[&str],
+    validation_errors: &[XmbValidationError],
+) -> EditorResponse {
+    let mut open = true;
+    let mut changed = false;
+    let mut saved = false;
+
+    let title = folder_editor_title(folder_name, file_name);
+    egui::Window::new(format!("Xmb Editor ({title})"))
+        .open(&mut open)
+        .resizable(true)
+        .show(ctx, |ui| {
+            egui::MenuBar::new().ui(ui, |ui| {
+                ui.menu_button("File", |ui| {
+                    if ui.button("Save").clicked() {
+                        saved |= save_file(xmb, folder_name, file_name);
+                    }
+
+                    if ui.button("Save As...").clicked() {
+                        saved |= save_file_as(xmb, folder_name, file_name, "Xmb", "xmb");
+                    }
+                });
+            });
+            ui.separator();
+
+            display_validation_errors(ui, validation_errors.iter());
+
+            ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    changed |= edit_entries(ui, &mut xmb.entries, numdlb_names, Id::new("xmb"));
+
+                    if ui.button("Add Entry").clicked() {
+                        xmb.entries.push(new_entry());
+                        changed = true;
+                    }
+                });
+        });
+
+    EditorResponse {
+        open,
+        changed,
+        saved,
+        message: None,
+    }
+}
+
+fn edit_entries(ui: &mut Ui, entries: &mut Vec<XmbEntry>, numdlb_names: &[&str], id: Id) -> bool {
+    let mut changed = false;
+    let mut entry_to_remove = None;
+
+    for (i, entry) in entries.iter_mut().enumerate() {
+        let id = id.with(i);
+        CollapsingHeader::new(RichText::new(&entry.name).heading())
+            .id_salt(id)
+            .default_open(true)
+            .show(ui, |ui| {
+                Grid::new(id.with("attributes")).show(ui, |ui| {
+                    ui.label("Name");
+                    changed |= ui.text_edit_singleline(&mut entry.name).changed();
+                    ui.end_row();
+                });
+                changed |= edit_attributes(ui, entry, numdlb_names, id);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Add Attribute").clicked() {
+                        add_attribute(entry);
+                        changed = true;
+                    }
+
+                    if ui.button("Add Child Entry").clicked() {
+                        entry.entries.push(new_entry());
+                        changed = true;
+                    }
+
+                    if ui.button("Remove Entry").clicked() {
+                        entry_to_remove = Some(i);
+                    }
+                });
+
+                changed |= edit_entries(ui, &mut entry.entries, numdlb_names, id);
+            });
+    }
+
+    if let Some(i) = entry_to_remove {
+        entries.remove(i);
+        changed = true;
+    }
+
+    changed
+}
+
+fn edit_attributes(ui: &mut Ui, entry: &mut XmbEntry, numdlb_names: &[&str], id: Id) -> bool {
+    let names: Vec<_> = entry.attributes.keys().cloned().collect();
+
+    let mut changed = false;
+    let mut attribute_to_remove = None;
+    let mut attribute_to_rename = None;
+
+    Grid::new(id.with("attribute_values")).show(ui, |ui| {
+        for (i, name) in names.iter().enumerate() {
+            // Attribute names are map keys, so only rename once editing is finished.
+            // This avoids replacing other attributes with the same name while typing.
+            let name_id = id.with(i).with("name");
+            let mut new_name = ui
+                .memory_mut(|m| m.data.get_temp::<String>(name_id))
+                .unwrap_or_else(|| name.clone());
+
+            let response = ui.add(
+                TextEdit::singleline(&mut new_name)
+                    .id(name_id)
+                    .desired_width(120.0),
+            );
+            let is_valid_name = is_valid_attribute_name(entry, name, &new_name);
+            if response.lost_focus() {
+                ui.memory_mut(|m| m.data.remove::<String>(name_id));
+                if is_valid_name && &new_name != name {
+                    attribute_to_rename = Some((name.clone(), new_name.clone()));
+                }
+            } else if response.has_focus() {
+                ui.memory_mut(|m| m.data.insert_temp(name_id, new_name.clone()));
+            }
+
+            if let Some(value) = entry.attributes.get_mut(name) {
+                if is_numdlb_file_name(value) {
+                    changed |= numdlb_combo_box(ui, value, id.with(i), numdlb_names);
+                } else {
+                    changed |= ui
+                        .add(TextEdit::singleline(value).desired_width(200.0))
+                        .changed();
+                }
+            }
+
+            if ui.button("Remove").clicked() {
+                attribute_to_remove = Some(name.clone());
+            }
+
+            if !is_valid_name {
+                ui.label(warning_icon_text(
+                    "Attribute names must be unique and not empty.",
+                ));
+            }
+            ui.end_row();
+        }
+    });
+
+    if let Some(name) = attribute_to_remove {
+        remove_attribute(entry, &name);
+        changed = true;
+    }
+
+    if let Some((name, new_name)) = attribute_to_rename {
+        changed |= rename_attribute(entry, &name, &new_name);
+    }
+
+    changed
+}
+
+/// Add an empty attribute with a unique name to `entry`.
+fn add_attribute(entry: &mut XmbEntry) {
+    let name = (0..)
+        .map(|i| format!("attribute{i}"))
+        .find(|name| !entry.attributes.contains_key(name))
+        .unwrap();
+    entry.attributes.insert(name, String::new());
+}
+
+fn remove_attribute(entry: &mut XmbEntry, name: &str) {
+    // Rebuild the attributes to preserve the order of the remaining attributes.
+    entry.attributes = std::mem::take(&mut entry.attributes)
+        .into_iter()
+        .filter(|(k, _)| k != name)
+        .collect();
+}
+
+fn is_valid_attribute_name(entry: &XmbEntry, name: &str, new_name: &str) -> bool {
+    !new_name.is_empty() && (new_name == name || !entry.attributes.contains_key(new_name))
+}
+
+/// Rename the attribute `name` to `new_name` without changing the attribute order.
+/// Returns `false` without renaming if `new_name` is empty or already used by another attribute.
+fn rename_attribute(entry: &mut XmbEntry, name: &str, new_name: &str) -> bool {
+    if new_name == name
+        || !entry.attributes.contains_key(name)
+        || !is_valid_attribute_name(entry, name, new_name)
+    {
+        return false;
+    }
+
+    entry.attributes = std::mem::take(&mut entry.attributes)
+        .into_iter()
+        .map(|(k, v)| {
+            if k == name {
+                (new_name.to_owned(), v)
+            } else {
+                (k, v)
+            }
+        })
+        .collect();
+    true
+}
+
+fn numdlb_combo_box(ui: &mut Ui, value: &mut String, id: Id, numdlb_names: &[&str]) -> bool {
+    let mut changed = false;
+
+    let is_valid = numdlb_names.iter().any(|n| n.eq_ignore_ascii_case(value));
+    let text = if is_valid {
+        RichText::new(value.as_str())
+    } else {
+        warning_icon_text(value)
+    };
+
+    ComboBox::from_id_salt(id)
+        .width(200.0)
+        .selected_text(text)
+        .show_ui(ui, |ui| {
+            for name in numdlb_names {
+                changed |= ui
+                    .selectable_value(value, name.to_string(), *name)
+                    .changed();
+            }
+        });
+
+    changed
+}
+
+fn new_entry() -> XmbEntry {
+    XmbEntry {
+        name: "entry".to_owned(),
+        attributes: Default::default(),
+        entries: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attributes(entry: &XmbEntry) -> Vec<(&str, &str)> {
+        entry
+            .attributes
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn add_remove_rename_attributes() {
+        let mut entry = new_entry();
+        add_attribute(&mut entry);
+        add_attribute(&mut entry);
+        add_attribute(&mut entry);
+        assert_eq!(
+            vec![("attribute0", ""), ("attribute1", ""), ("attribute2", "")],
+            attributes(&entry)
+        );
+
+        remove_attribute(&mut entry, "attribute1");
+        assert_eq!(
+            vec![("attribute0", ""), ("attribute2", "")],
+            attributes(&entry)
+        );
+
+        // Removed names should be reused.
+        add_attribute(&mut entry);
+        assert_eq!(
+            vec![("attribute0", ""), ("attribute2", ""), ("attribute1", "")],
+            attributes(&entry)
+        );
+
+        entry
+            .attributes
+            .insert("attribute2".to_owned(), "model.numdlb".to_owned());
+        assert!(rename_attribute(&mut entry, "attribute2", "name"));
+        assert_eq!(
+            vec![
+                ("attribute0", ""),
+                ("name", "model.numdlb"),
+                ("attribute1", "")
+            ],
+            attributes(&entry)
+        );
+    }
+
+    #[test]
+    fn rename_attribute_duplicate_name() {
+        let mut entry = new_entry();
+        entry.attributes.insert("a".to_owned(), "1".to_owned());
+        entry.attributes.insert("b".to_owned(), "2".to_owned());
+
+        // Renaming shouldn't replace the value for an existing name.
+        assert!(!rename_attribute(&mut entry, "a", "b"));
+        assert!(!rename_attribute(&mut entry, "a", ""));
+        assert!(!rename_attribute(&mut entry, "c", "d"));
+        assert_eq!(vec![("a", "1"), ("b", "2")], attributes(&entry));
+    }
+}
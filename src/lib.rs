@@ -22,16 +22,22 @@ use std::{
     sync::Arc,
 };
 use thumbnail::Thumbnail;
+use xmb_lib::XmbFile;
 
 pub mod app;
+pub mod bounds;
 pub mod capture;
 pub mod editors;
 pub mod log;
 pub mod material;
 pub mod model_folder;
+pub mod modl;
 pub mod path;
 pub mod preferences;
 pub mod presets;
+pub mod statistics;
+pub mod swing;
+pub mod texture;
 pub mod thumbnail;
 pub mod update;
 pub mod validation;
@@ -55,6 +61,8 @@ pub enum EditorMessage {
         mesh_object_name: String,
         mesh_object_subindex: u64,
     },
+    AutoNumberSubindices,
+    MatchMeshOrder,
 }
 
 impl EditorResponse {
@@ -840,7 +848,41 @@ fn hide_ink_meshes(render_model: &mut RenderModel) {
     }
 }
 
-fn save_file<T: SsbhData>(file: &T, folder_name: &Path, file_name: &str) -> bool {
+/// A file format that can be saved from an editor.
+trait WriteToFile {
+    fn write_to_file(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+macro_rules! impl_write_to_file {
+    ($($ty:ty),*) => {
+        $(
+            impl WriteToFile for $ty {
+                fn write_to_file(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+                    SsbhData::write_to_file(self, path).map_err(Into::into)
+                }
+            }
+        )*
+    };
+}
+
+impl_write_to_file!(
+    AdjData, AnimData, HlpbData, MatlData, MeshData, MeshExData, ModlData, SkelData
+);
+
+impl WriteToFile for NutexbFile {
+    fn write_to_file(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        // Inherent methods take priority over the trait method.
+        NutexbFile::write_to_file(self, path).map_err(Into::into)
+    }
+}
+
+impl WriteToFile for XmbFile {
+    fn write_to_file(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        XmbFile::write_to_file(self, path).map_err(Into::into)
+    }
+}
+
+fn save_file<T: WriteToFile>(file: &T, folder_name: &Path, file_name: &str) -> bool {
     let file_path = Path::new(folder_name).join(file_name);
     if let Err(e) = file.write_to_file(&file_path) {
         error!("Failed to save {file_path:?}: {e}");
@@ -850,7 +892,7 @@ fn save_file<T: SsbhData>(file: &T, folder_name: &Path, file_name: &str) -> bool
     }
 }
 
-fn save_file_as<T: SsbhData>(
+fn save_file_as<T: WriteToFile>(
     file: &T,
     folder_name: &Path,
     file_name: &str,
@@ -16,10 +16,10 @@ use ssbh_editor::{
     AnimationState, CameraState, RenderState, SwingState,
     app::{RenderAction, SsbhApp, UiState},
     checkerboard_texture, default_fonts, default_text_styles,
-    material::load_material_presets,
-    path::{PROJECT_DIR, presets_file},
+    material::{MaterialVariantRule, load_material_variant_rules},
+    path::{PROJECT_DIR, material_variants_file, preset_library_file, presets_file},
     preferences::{AppPreferences, GraphicsBackend},
-    presets::default_presets,
+    presets::{MaterialPreset, default_presets, load_preset_library},
     thumbnail::{Thumbnail, generate_default_thumbnails},
     update::{LatestReleaseInfo, check_for_updates},
     update_color_theme, widgets_dark,
@@ -59,8 +59,8 @@ fn main() {
 
     let release_info = check_for_updates();
 
-    let presets_file = presets_file();
-    let material_presets = load_material_presets(presets_file);
+    let material_presets = load_preset_library(preset_library_file(), presets_file());
+    let material_variant_rules = load_material_variant_rules(material_variants_file());
 
     let preferred_backends = match preferences.graphics_backend {
         GraphicsBackend::Auto => wgpu::Backends::PRIMARY,
@@ -189,6 +189,7 @@ fn main() {
                 default_thumbnails,
                 release_info,
                 material_presets,
+                material_variant_rules,
                 red_checkerboard,
                 yellow_checkerboard,
                 camera_state,
@@ -204,10 +205,12 @@ fn main() {
 }
 
 // TODO: Make this a method.
+#[allow(clippy::too_many_arguments)]
 fn create_app(
     default_thumbnails: Vec<Thumbnail>,
     release_info: LatestReleaseInfo,
-    material_presets: Vec<ssbh_data::matl_data::MatlEntryData>,
+    material_presets: Vec<MaterialPreset>,
+    material_variant_rules: Vec<MaterialVariantRule>,
     red_checkerboard: egui::TextureId,
     yellow_checkerboard: egui::TextureId,
     camera_state: CameraState,
@@ -222,6 +225,7 @@ fn create_app(
         should_update_thumbnails: false,
         material_presets,
         default_presets: default_presets(),
+        material_variant_rules,
         red_checkerboard,
         yellow_checkerboard,
         draw_bone_names: false,
@@ -1,7 +1,14 @@
 use log::error;
-use ssbh_data::{Vector4, matl_data::*};
+use serde::{Deserialize, Serialize};
+use ssbh_data::{
+    Vector4,
+    anim_data::{AnimData, GroupType},
+    matl_data::*,
+    modl_data::ModlEntryData,
+};
 use ssbh_wgpu::{ShaderProgram, split_param};
-use std::str::FromStr;
+use std::{collections::BTreeSet, str::FromStr};
+use strum::{EnumIter, IntoEnumIterator};
 
 const UV_TRANSFORM_IDENTITY: UvTransform = UvTransform {
     scale_u: 1.0,
@@ -320,6 +327,397 @@ pub fn remove_parameters(entry: &mut MatlEntryData, parameters: &[ParamId]) {
     entry.textures.sort_by_key(|p| p.param_id as u64);
 }
 
+/// Access the parameters with data type `Self` for a material.
+pub trait MatlParam: Sized {
+    fn params(entry: &MatlEntryData) -> &[ParamData<Self>];
+    fn params_mut(entry: &mut MatlEntryData) -> &mut Vec<ParamData<Self>>;
+}
+
+macro_rules! impl_matl_param {
+    ($ty:ty, $field:ident) => {
+        impl MatlParam for $ty {
+            fn params(entry: &MatlEntryData) -> &[ParamData<Self>] {
+                &entry.$field
+            }
+
+            fn params_mut(entry: &mut MatlEntryData) -> &mut Vec<ParamData<Self>> {
+                &mut entry.$field
+            }
+        }
+    };
+}
+
+impl_matl_param!(BlendStateData, blend_states);
+impl_matl_param!(f32, floats);
+impl_matl_param!(bool, booleans);
+impl_matl_param!(Vector4, vectors);
+impl_matl_param!(RasterizerStateData, rasterizer_states);
+impl_matl_param!(SamplerData, samplers);
+impl_matl_param!(String, textures);
+impl_matl_param!(UvTransform, uv_transforms);
+
+/// A parameter value for multiple selected materials.
+#[derive(Debug, PartialEq, Clone)]
+pub enum BatchValue<T> {
+    /// All materials have the parameter with the same value.
+    Shared(T),
+    /// The values differ or the parameter is missing from some materials.
+    /// The first value is used as the starting point for editing.
+    Mixed(T),
+}
+
+impl<T> BatchValue<T> {
+    pub fn into_value(self) -> T {
+        match self {
+            BatchValue::Shared(v) => v,
+            BatchValue::Mixed(v) => v,
+        }
+    }
+
+    pub fn is_mixed(&self) -> bool {
+        matches!(self, BatchValue::Mixed(_))
+    }
+}
+
+/// The parameters with data type `T` in any of the `entries` sorted by ID.
+pub fn batch_param_ids<T: MatlParam>(entries: &[&MatlEntryData]) -> Vec<ParamId> {
+    let mut param_ids: Vec<_> = entries
+        .iter()
+        .flat_map(|e| T::params(e).iter().map(|p| p.param_id))
+        .collect();
+    param_ids.sort_by_key(|p| *p as u64);
+    param_ids.dedup();
+    param_ids
+}
+
+/// The value of `field` for `param_id` across all `entries`
+/// or `None` if no entry has the parameter.
+pub fn batch_value<T: MatlParam, U: PartialEq + Clone>(
+    entries: &[&MatlEntryData],
+    param_id: ParamId,
+    field: impl Fn(&T) -> &U,
+) -> Option<BatchValue<U>> {
+    let values: Vec<_> = entries
+        .iter()
+        .map(|e| {
+            T::params(e)
+                .iter()
+                .find(|p| p.param_id == param_id)
+                .map(|p| field(&p.data))
+        })
+        .collect();
+
+    let first = values.iter().flatten().next()?;
+    if values.iter().all(|v| *v == Some(*first)) {
+        Some(BatchValue::Shared((*first).clone()))
+    } else {
+        Some(BatchValue::Mixed((*first).clone()))
+    }
+}
+
+/// The number of `entries` without `param_id`.
+pub fn batch_missing_count<T: MatlParam>(entries: &[&MatlEntryData], param_id: ParamId) -> usize {
+    entries
+        .iter()
+        .filter(|e| !T::params(e).iter().any(|p| p.param_id == param_id))
+        .count()
+}
+
+/// Set `field` for `param_id` to `value` for all `entries`.
+/// Entries without the parameter are skipped unless `add_missing` is `true`.
+pub fn set_batch_value<T: MatlParam, U: Clone>(
+    entries: &mut [&mut MatlEntryData],
+    param_id: ParamId,
+    field: impl Fn(&mut T) -> &mut U,
+    value: &U,
+    add_missing: bool,
+) {
+    for entry in entries.iter_mut() {
+        if add_missing && !T::params(entry).iter().any(|p| p.param_id == param_id) {
+            add_parameters(entry, &[param_id]);
+        }
+
+        if let Some(param) = T::params_mut(entry)
+            .iter_mut()
+            .find(|p| p.param_id == param_id)
+        {
+            *field(&mut param.data) = value.clone();
+        }
+    }
+}
+
+/// The parameter with data type `T` for `param_id` if present.
+pub fn find_param<T: MatlParam>(entry: &MatlEntryData, param_id: ParamId) -> Option<&ParamData<T>> {
+    T::params(entry).iter().find(|p| p.param_id == param_id)
+}
+
+/// How a parameter differs between two materials.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ParamDiff {
+    Equal,
+    Different,
+    /// The parameter is only present in the first material.
+    FirstOnly,
+    /// The parameter is only present in the second material.
+    SecondOnly,
+}
+
+/// Compare the parameters of `a` and `b` grouped by type and sorted by ID.
+pub fn diff_materials(a: &MatlEntryData, b: &MatlEntryData) -> Vec<(ParamId, ParamDiff)> {
+    let mut diffs = Vec::new();
+    diff_params::<bool>(a, b, &mut diffs);
+    diff_params::<f32>(a, b, &mut diffs);
+    diff_params::<Vector4>(a, b, &mut diffs);
+    diff_params::<String>(a, b, &mut diffs);
+    diff_params::<SamplerData>(a, b, &mut diffs);
+    diff_params::<UvTransform>(a, b, &mut diffs);
+    diff_params::<RasterizerStateData>(a, b, &mut diffs);
+    diff_params::<BlendStateData>(a, b, &mut diffs);
+    diffs
+}
+
+fn diff_params<T: MatlParam + PartialEq>(
+    a: &MatlEntryData,
+    b: &MatlEntryData,
+    diffs: &mut Vec<(ParamId, ParamDiff)>,
+) {
+    let mut param_ids: Vec<_> = T::params(a)
+        .iter()
+        .chain(T::params(b))
+        .map(|p| p.param_id)
+        .collect();
+    param_ids.sort_by_key(|p| *p as u64);
+    param_ids.dedup();
+
+    for param_id in param_ids {
+        let diff = match (find_param::<T>(a, param_id), find_param::<T>(b, param_id)) {
+            (Some(pa), Some(pb)) if pa.data == pb.data => ParamDiff::Equal,
+            (Some(_), Some(_)) => ParamDiff::Different,
+            (Some(_), None) => ParamDiff::FirstOnly,
+            (None, _) => ParamDiff::SecondOnly,
+        };
+        diffs.push((param_id, diff));
+    }
+}
+
+/// Copy the value for `param_id` from `source` to `target`.
+/// Returns `false` if `source` does not have the parameter.
+pub fn copy_parameter(
+    target: &mut MatlEntryData,
+    source: &MatlEntryData,
+    param_id: ParamId,
+) -> bool {
+    copy_param::<bool>(target, source, param_id)
+        || copy_param::<f32>(target, source, param_id)
+        || copy_param::<Vector4>(target, source, param_id)
+        || copy_param::<String>(target, source, param_id)
+        || copy_param::<SamplerData>(target, source, param_id)
+        || copy_param::<UvTransform>(target, source, param_id)
+        || copy_param::<RasterizerStateData>(target, source, param_id)
+        || copy_param::<BlendStateData>(target, source, param_id)
+}
+
+fn copy_param<T: MatlParam + Clone>(
+    target: &mut MatlEntryData,
+    source: &MatlEntryData,
+    param_id: ParamId,
+) -> bool {
+    let Some(param) = find_param::<T>(source, param_id) else {
+        return false;
+    };
+
+    let params = T::params_mut(target);
+    if let Some(target_param) = params.iter_mut().find(|p| p.param_id == param_id) {
+        target_param.data = param.data.clone();
+    } else {
+        params.push(ParamData::new(param_id, param.data.clone()));
+        // Sort the parameters to match Smash Ultimate's conventions.
+        params.sort_by_key(|p| p.param_id as u64);
+    }
+    true
+}
+
+/// A group of parameters with the same data type.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, EnumIter)]
+pub enum ParamGroup {
+    Booleans,
+    Floats,
+    Vectors,
+    Textures,
+    Samplers,
+    UvTransforms,
+    RasterizerStates,
+    BlendStates,
+}
+
+impl ParamGroup {
+    pub fn name(self) -> &'static str {
+        match self {
+            ParamGroup::Booleans => "Booleans",
+            ParamGroup::Floats => "Floats",
+            ParamGroup::Vectors => "Vectors",
+            ParamGroup::Textures => "Textures",
+            ParamGroup::Samplers => "Samplers",
+            ParamGroup::UvTransforms => "UV Transforms",
+            ParamGroup::RasterizerStates => "Rasterizer States",
+            ParamGroup::BlendStates => "Blend States",
+        }
+    }
+
+    /// The parameters in this group for `entry` in order.
+    pub fn param_ids(self, entry: &MatlEntryData) -> Vec<ParamId> {
+        fn ids<T: MatlParam>(entry: &MatlEntryData) -> Vec<ParamId> {
+            T::params(entry).iter().map(|p| p.param_id).collect()
+        }
+
+        match self {
+            ParamGroup::Booleans => ids::<bool>(entry),
+            ParamGroup::Floats => ids::<f32>(entry),
+            ParamGroup::Vectors => ids::<Vector4>(entry),
+            ParamGroup::Textures => ids::<String>(entry),
+            ParamGroup::Samplers => ids::<SamplerData>(entry),
+            ParamGroup::UvTransforms => ids::<UvTransform>(entry),
+            ParamGroup::RasterizerStates => ids::<RasterizerStateData>(entry),
+            ParamGroup::BlendStates => ids::<BlendStateData>(entry),
+        }
+    }
+}
+
+/// A copy of `entry` with only the parameters in `param_ids`.
+/// This allows copying individual parameters or groups using the same format as whole materials.
+pub fn filter_parameters(entry: &MatlEntryData, param_ids: &[ParamId]) -> MatlEntryData {
+    let mut filtered = MatlEntryData {
+        material_label: entry.material_label.clone(),
+        shader_label: entry.shader_label.clone(),
+        blend_states: Vec::new(),
+        floats: Vec::new(),
+        booleans: Vec::new(),
+        vectors: Vec::new(),
+        rasterizer_states: Vec::new(),
+        samplers: Vec::new(),
+        textures: Vec::new(),
+        uv_transforms: Vec::new(),
+    };
+    for param_id in param_ids {
+        copy_parameter(&mut filtered, entry, *param_id);
+    }
+    filtered
+}
+
+/// Copy all parameters from `source` to `target`, replacing existing values.
+pub fn paste_parameters(target: &mut MatlEntryData, source: &MatlEntryData) {
+    for group in ParamGroup::iter() {
+        for param_id in group.param_ids(source) {
+            copy_parameter(target, source, param_id);
+        }
+    }
+}
+
+/// Assign nutexb files ending with `suffix` to the texture parameter `param_id`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct TextureSuffixRule {
+    pub suffix: String,
+    pub param_id: ParamId,
+}
+
+impl TextureSuffixRule {
+    pub fn new(suffix: &str, param_id: ParamId) -> Self {
+        Self {
+            suffix: suffix.to_owned(),
+            param_id,
+        }
+    }
+}
+
+pub fn default_texture_suffix_rules() -> Vec<TextureSuffixRule> {
+    // Smash Ultimate textures mostly follow the same naming conventions.
+    vec![
+        TextureSuffixRule::new("_col", ParamId::Texture0),
+        TextureSuffixRule::new("_nor", ParamId::Texture4),
+        TextureSuffixRule::new("_emi", ParamId::Texture5),
+        TextureSuffixRule::new("_prm", ParamId::Texture6),
+    ]
+}
+
+/// A change to a texture parameter from [auto_assign_textures].
+#[derive(Debug, PartialEq, Clone)]
+pub struct TextureAssignment {
+    pub param_id: ParamId,
+    pub old_texture: String,
+    pub new_texture: String,
+}
+
+/// Find textures for the texture parameters in `entry` using the suffix `rules`.
+///
+/// Textures with a name matching the material label or the currently assigned textures are preferred.
+/// Parameters without a matching texture use the default texture if the assigned texture is not in `texture_names`.
+pub fn auto_assign_textures(
+    entry: &MatlEntryData,
+    texture_names: &[&str],
+    rules: &[TextureSuffixRule],
+) -> Vec<TextureAssignment> {
+    // Empty suffixes would match every texture.
+    let rules: Vec<_> = rules.iter().filter(|r| !r.suffix.is_empty()).collect();
+    let suffixes: Vec<_> = rules.iter().map(|r| r.suffix.to_lowercase()).collect();
+
+    // Assume related textures share the same name except for the prefix and suffix.
+    let base_names: Vec<_> = std::iter::once(entry.material_label.as_str())
+        .chain(entry.textures.iter().map(|t| t.data.as_str()))
+        .map(|name| texture_base_name(name, &suffixes))
+        .collect();
+
+    entry
+        .textures
+        .iter()
+        .filter_map(|param| {
+            let candidates: Vec<_> = texture_names
+                .iter()
+                .filter(|name| {
+                    let name = name.to_lowercase();
+                    rules.iter().any(|r| {
+                        r.param_id == param.param_id && name.ends_with(&r.suffix.to_lowercase())
+                    })
+                })
+                .collect();
+
+            let new_texture = candidates
+                .iter()
+                .find(|name| base_names.contains(&texture_base_name(name, &suffixes)))
+                .or_else(|| match candidates.as_slice() {
+                    [name] => Some(name),
+                    _ => None,
+                })
+                .map(|name| name.to_string())
+                .or_else(|| {
+                    let is_valid = texture_names
+                        .iter()
+                        .any(|name| name.eq_ignore_ascii_case(&param.data));
+                    (!is_valid).then(|| default_texture(param.param_id).to_owned())
+                })?;
+
+            (!new_texture.eq_ignore_ascii_case(&param.data)).then(|| TextureAssignment {
+                param_id: param.param_id,
+                old_texture: param.data.clone(),
+                new_texture,
+            })
+        })
+        .collect()
+}
+
+fn texture_base_name(name: &str, suffixes: &[String]) -> String {
+    let name = name.to_lowercase();
+    let name = suffixes
+        .iter()
+        .find_map(|s| name.strip_suffix(s.as_str()))
+        .unwrap_or(&name);
+    // Color textures often use def or alp prefixes.
+    let name = name
+        .strip_prefix("def_")
+        .or_else(|| name.strip_prefix("alp_"))
+        .unwrap_or(name);
+    name.to_owned()
+}
+
 // TODO: Move this to ssbh_wgpu?
 pub fn is_vector(p: ParamId) -> bool {
     matches!(
@@ -742,9 +1140,169 @@ pub fn vector4_labels_long(p: ParamId) -> [&'static str; 4] {
     }
 }
 
+/// Materials used by the game without being assigned in the modl.
+/// The variants are only used if `material_label` is assigned to a mesh.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct MaterialVariantRule {
+    pub material_label: String,
+    pub variant_labels: Vec<String>,
+    #[serde(default)]
+    pub description: String,
+}
+
+pub fn default_material_variant_rules() -> Vec<MaterialVariantRule> {
+    // The game swaps eye materials for World of Light and Final Smash.
+    ["L", "R"]
+        .into_iter()
+        .flat_map(|side| {
+            ["", "1", "2"].into_iter().map(move |index| MaterialVariantRule {
+                material_label: format!("Eye{side}{index}"),
+                variant_labels: ["D", "G", "L"]
+                    .iter()
+                    .map(|variant| format!("Eye{side}{variant}{index}"))
+                    .collect(),
+                description: "World of Light purple (D), Final Smash (G), and World of Light red (L) eyes."
+                    .to_owned(),
+            })
+        })
+        .collect()
+}
+
+pub fn load_material_variant_rules<P: AsRef<std::path::Path>>(path: P) -> Vec<MaterialVariantRule> {
+    // The application doesn't ship with a rules file.
+    // Use the default rules if not found so users only need the file to customize rules.
+    std::fs::read(path.as_ref())
+        .and_then(|data| Ok(serde_json::from_slice(&data)?))
+        .map_err(|e| {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                error!(
+                    "Failed to load material variant rules from {:?}: {}",
+                    path.as_ref(),
+                    e
+                );
+            }
+            e
+        })
+        .unwrap_or_else(|_| default_material_variant_rules())
+}
+
+/// Rename the variants of `old_label` in `entries` to the corresponding variants of `new_label`.
+/// Variants are matched by their position in each rule's variant labels.
+/// Returns `true` if any materials were renamed.
+pub fn rename_material_variants(
+    entries: &mut [MatlEntryData],
+    old_label: &str,
+    new_label: &str,
+    rules: &[MaterialVariantRule],
+) -> bool {
+    let find_rule = |label| rules.iter().find(|r| r.material_label == label);
+    let (Some(old_rule), Some(new_rule)) = (find_rule(old_label), find_rule(new_label)) else {
+        return false;
+    };
+
+    let mut changed = false;
+    for (old_variant, new_variant) in old_rule.variant_labels.iter().zip(&new_rule.variant_labels) {
+        // Avoid creating duplicate material labels.
+        if entries.iter().any(|e| &e.material_label == new_variant) {
+            continue;
+        }
+        for entry in entries
+            .iter_mut()
+            .filter(|e| &e.material_label == old_variant)
+        {
+            entry.material_label.clone_from(new_variant);
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Find the labels of materials assigned in the modl or implicitly used by variant `rules`.
+pub fn used_material_labels(
+    modl_entries: &[ModlEntryData],
+    rules: &[MaterialVariantRule],
+) -> BTreeSet<String> {
+    let mut labels: BTreeSet<_> = modl_entries
+        .iter()
+        .map(|e| e.material_label.clone())
+        .collect();
+
+    let variants: Vec<_> = rules
+        .iter()
+        .filter(|r| labels.contains(&r.material_label))
+        .flat_map(|r| r.variant_labels.iter().cloned())
+        .collect();
+    labels.extend(variants);
+
+    labels
+}
+
+/// The mesh objects and animations referencing a material.
+#[derive(Debug, PartialEq, Default)]
+pub struct MaterialUsage {
+    /// The name and subindex of mesh objects assigned in the modl.
+    pub mesh_objects: Vec<(String, u64)>,
+    /// The animation file name and track name for material animations.
+    pub anim_tracks: Vec<(String, String)>,
+    /// The assigned material that implicitly uses this variant material.
+    pub variant_of: Option<String>,
+}
+
+impl MaterialUsage {
+    /// Returns `true` if the material is not assigned or used as a variant.
+    /// Animations alone do not make a material visible in game.
+    pub fn is_unused(&self) -> bool {
+        self.mesh_objects.is_empty() && self.variant_of.is_none()
+    }
+}
+
+pub fn material_usage(
+    material_label: &str,
+    modl_entries: &[ModlEntryData],
+    anims: &[(String, Option<AnimData>)],
+    rules: &[MaterialVariantRule],
+) -> MaterialUsage {
+    let mesh_objects = modl_entries
+        .iter()
+        .filter(|e| e.material_label == material_label)
+        .map(|e| (e.mesh_object_name.clone(), e.mesh_object_subindex))
+        .collect();
+
+    let anim_tracks = anims
+        .iter()
+        .filter_map(|(name, anim)| Some((name, anim.as_ref()?)))
+        .flat_map(|(name, anim)| {
+            anim.groups
+                .iter()
+                .filter(|g| g.group_type == GroupType::Material)
+                .flat_map(|g| &g.nodes)
+                .filter(|n| n.name == material_label)
+                .flat_map(|n| &n.tracks)
+                .map(move |t| (name.clone(), t.name.clone()))
+        })
+        .collect();
+
+    let variant_of = rules
+        .iter()
+        .find(|r| {
+            r.variant_labels.iter().any(|l| l == material_label)
+                && modl_entries
+                    .iter()
+                    .any(|e| e.material_label == r.material_label)
+        })
+        .map(|r| r.material_label.clone());
+
+    MaterialUsage {
+        mesh_objects,
+        anim_tracks,
+        variant_of,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ssbh_data::anim_data::{GroupData, NodeData, TrackData, TrackValues};
 
     use pretty_assertions::assert_eq;
 
@@ -1166,4 +1724,381 @@ mod tests {
             entry
         );
     }
+
+    #[test]
+    fn batch_value_shared_mixed_missing() {
+        let a = MatlEntryData {
+            material_label: "a".to_owned(),
+            shader_label: String::new(),
+            blend_states: Vec::new(),
+            floats: vec![FloatParam::new(ParamId::CustomFloat0, 0.5)],
+            booleans: vec![BooleanParam::new(ParamId::CustomBoolean1, true)],
+            vectors: vec![Vector4Param::new(
+                ParamId::CustomVector47,
+                Vector4::new(1.0, 0.5, 1.0, 0.2),
+            )],
+            rasterizer_states: Vec::new(),
+            samplers: Vec::new(),
+            textures: Vec::new(),
+            uv_transforms: Vec::new(),
+        };
+        let b = MatlEntryData {
+            material_label: "b".to_owned(),
+            shader_label: String::new(),
+            blend_states: Vec::new(),
+            floats: Vec::new(),
+            booleans: vec![BooleanParam::new(ParamId::CustomBoolean1, true)],
+            vectors: vec![Vector4Param::new(
+                ParamId::CustomVector47,
+                Vector4::new(0.0, 0.5, 1.0, 0.2),
+            )],
+            rasterizer_states: Vec::new(),
+            samplers: Vec::new(),
+            textures: Vec::new(),
+            uv_transforms: Vec::new(),
+        };
+        let entries = [&a, &b];
+
+        assert_eq!(
+            vec![ParamId::CustomFloat0],
+            batch_param_ids::<f32>(&entries)
+        );
+        assert_eq!(
+            Some(BatchValue::Shared(true)),
+            batch_value::<bool, _>(&entries, ParamId::CustomBoolean1, |d| d)
+        );
+        assert_eq!(
+            Some(BatchValue::Mixed(0.5)),
+            batch_value::<f32, _>(&entries, ParamId::CustomFloat0, |d| d)
+        );
+        assert_eq!(
+            1,
+            batch_missing_count::<f32>(&entries, ParamId::CustomFloat0)
+        );
+        assert_eq!(
+            Some(BatchValue::Mixed(1.0)),
+            batch_value::<Vector4, _>(&entries, ParamId::CustomVector47, |d| &d.x)
+        );
+        assert_eq!(
+            Some(BatchValue::Shared(0.5)),
+            batch_value::<Vector4, _>(&entries, ParamId::CustomVector47, |d| &d.y)
+        );
+        assert_eq!(
+            None,
+            batch_value::<f32, _>(&entries, ParamId::CustomFloat1, |d| d)
+        );
+    }
+
+    #[test]
+    fn set_batch_value_add_missing() {
+        let mut a = MatlEntryData {
+            material_label: "a".to_owned(),
+            shader_label: String::new(),
+            blend_states: Vec::new(),
+            floats: Vec::new(),
+            booleans: Vec::new(),
+            vectors: Vec::new(),
+            rasterizer_states: Vec::new(),
+            samplers: vec![SamplerParam::new(
+                ParamId::Sampler0,
+                SamplerData {
+                    wraps: WrapMode::ClampToEdge,
+                    ..Default::default()
+                },
+            )],
+            textures: Vec::new(),
+            uv_transforms: Vec::new(),
+        };
+        let mut b = MatlEntryData {
+            material_label: "b".to_owned(),
+            shader_label: String::new(),
+            blend_states: Vec::new(),
+            floats: Vec::new(),
+            booleans: Vec::new(),
+            vectors: Vec::new(),
+            rasterizer_states: Vec::new(),
+            samplers: Vec::new(),
+            textures: Vec::new(),
+            uv_transforms: Vec::new(),
+        };
+
+        let mut entries = [&mut a, &mut b];
+        set_batch_value::<SamplerData, _>(
+            &mut entries,
+            ParamId::Sampler0,
+            |d| &mut d.wrapt,
+            &WrapMode::MirroredRepeat,
+            false,
+        );
+        assert_eq!(WrapMode::ClampToEdge, entries[0].samplers[0].data.wraps);
+        assert_eq!(WrapMode::MirroredRepeat, entries[0].samplers[0].data.wrapt);
+        assert!(entries[1].samplers.is_empty());
+
+        set_batch_value::<SamplerData, _>(
+            &mut entries,
+            ParamId::Sampler0,
+            |d| &mut d.wraps,
+            &WrapMode::Repeat,
+            true,
+        );
+        assert_eq!(WrapMode::Repeat, entries[0].samplers[0].data.wraps);
+        assert_eq!(WrapMode::MirroredRepeat, entries[0].samplers[0].data.wrapt);
+        assert_eq!(
+            vec![SamplerParam::new(
+                ParamId::Sampler0,
+                SamplerData {
+                    wraps: WrapMode::Repeat,
+                    ..Default::default()
+                }
+            )],
+            entries[1].samplers
+        );
+    }
+
+    #[test]
+    fn diff_copy_materials() {
+        let mut a = MatlEntryData {
+            material_label: "a".to_owned(),
+            shader_label: String::new(),
+            blend_states: Vec::new(),
+            floats: vec![FloatParam::new(ParamId::CustomFloat0, 0.5)],
+            booleans: vec![BooleanParam::new(ParamId::CustomBoolean1, true)],
+            vectors: vec![Vector4Param::new(
+                ParamId::CustomVector47,
+                Vector4::new(1.0, 0.5, 1.0, 0.2),
+            )],
+            rasterizer_states: Vec::new(),
+            samplers: Vec::new(),
+            textures: Vec::new(),
+            uv_transforms: Vec::new(),
+        };
+        let b = MatlEntryData {
+            material_label: "b".to_owned(),
+            shader_label: String::new(),
+            blend_states: Vec::new(),
+            floats: Vec::new(),
+            booleans: vec![BooleanParam::new(ParamId::CustomBoolean1, true)],
+            vectors: vec![
+                Vector4Param::new(ParamId::CustomVector0, Vector4::new(1.0, 0.0, 0.0, 0.0)),
+                Vector4Param::new(ParamId::CustomVector47, Vector4::new(0.0, 0.5, 1.0, 0.2)),
+            ],
+            rasterizer_states: Vec::new(),
+            samplers: Vec::new(),
+            textures: Vec::new(),
+            uv_transforms: Vec::new(),
+        };
+
+        assert_eq!(
+            vec![
+                (ParamId::CustomBoolean1, ParamDiff::Equal),
+                (ParamId::CustomFloat0, ParamDiff::FirstOnly),
+                (ParamId::CustomVector0, ParamDiff::SecondOnly),
+                (ParamId::CustomVector47, ParamDiff::Different),
+            ],
+            diff_materials(&a, &b)
+        );
+
+        assert!(copy_parameter(&mut a, &b, ParamId::CustomVector47));
+        assert!(copy_parameter(&mut a, &b, ParamId::CustomVector0));
+        assert!(!copy_parameter(&mut a, &b, ParamId::CustomFloat0));
+        assert_eq!(b.vectors, a.vectors);
+        assert_eq!(vec![FloatParam::new(ParamId::CustomFloat0, 0.5)], a.floats);
+    }
+
+    #[test]
+    fn copy_paste_parameter_groups() {
+        let source = MatlEntryData {
+            material_label: "a".to_owned(),
+            shader_label: "SFX_PBS_0100000008008269_opaque".to_owned(),
+            blend_states: Vec::new(),
+            floats: vec![FloatParam::new(ParamId::CustomFloat0, 0.5)],
+            booleans: Vec::new(),
+            vectors: vec![
+                Vector4Param::new(ParamId::CustomVector0, Vector4::new(1.0, 0.0, 0.0, 0.0)),
+                Vector4Param::new(ParamId::CustomVector47, Vector4::new(0.0, 0.5, 1.0, 0.2)),
+            ],
+            rasterizer_states: Vec::new(),
+            samplers: Vec::new(),
+            textures: vec![TextureParam::new(ParamId::Texture0, "alp".to_owned())],
+            uv_transforms: Vec::new(),
+        };
+
+        let vectors = filter_parameters(&source, &ParamGroup::Vectors.param_ids(&source));
+        assert_eq!(source.vectors, vectors.vectors);
+        assert!(vectors.floats.is_empty());
+        assert!(vectors.textures.is_empty());
+
+        let mut target = MatlEntryData {
+            material_label: "b".to_owned(),
+            shader_label: String::new(),
+            blend_states: Vec::new(),
+            floats: Vec::new(),
+            booleans: Vec::new(),
+            vectors: vec![Vector4Param::new(
+                ParamId::CustomVector47,
+                Vector4::new(1.0, 1.0, 1.0, 1.0),
+            )],
+            rasterizer_states: Vec::new(),
+            samplers: Vec::new(),
+            textures: vec![TextureParam::new(ParamId::Texture0, "def".to_owned())],
+            uv_transforms: Vec::new(),
+        };
+        let texture = filter_parameters(&source, &[ParamId::Texture0]);
+        paste_parameters(&mut target, &texture);
+        paste_parameters(&mut target, &vectors);
+
+        assert_eq!("b", target.material_label);
+        assert_eq!("", target.shader_label);
+        assert_eq!(source.vectors, target.vectors);
+        assert_eq!(source.textures, target.textures);
+        assert!(target.floats.is_empty());
+    }
+
+    #[test]
+    fn auto_assign_textures_suffixes() {
+        let entry = MatlEntryData {
+            material_label: "alp_mario_002".to_owned(),
+            shader_label: String::new(),
+            blend_states: Vec::new(),
+            floats: Vec::new(),
+            booleans: Vec::new(),
+            vectors: Vec::new(),
+            rasterizer_states: Vec::new(),
+            samplers: Vec::new(),
+            textures: vec![
+                TextureParam::new(ParamId::Texture0, "old_col".to_owned()),
+                TextureParam::new(ParamId::Texture4, "mario_002_nor".to_owned()),
+                TextureParam::new(ParamId::Texture5, "missing".to_owned()),
+                TextureParam::new(ParamId::Texture6, "old_prm".to_owned()),
+            ],
+            uv_transforms: Vec::new(),
+        };
+
+        let texture_names = [
+            "def_mario_001_col",
+            "def_mario_002_col",
+            "mario_002_nor",
+            "MARIO_002_PRM",
+            "old_col",
+            "old_prm",
+        ];
+
+        assert_eq!(
+            vec![
+                TextureAssignment {
+                    param_id: ParamId::Texture0,
+                    old_texture: "old_col".to_owned(),
+                    new_texture: "def_mario_002_col".to_owned(),
+                },
+                TextureAssignment {
+                    param_id: ParamId::Texture5,
+                    old_texture: "missing".to_owned(),
+                    new_texture: "/common/shader/sfxpbs/default_black".to_owned(),
+                },
+                TextureAssignment {
+                    param_id: ParamId::Texture6,
+                    old_texture: "old_prm".to_owned(),
+                    new_texture: "MARIO_002_PRM".to_owned(),
+                },
+            ],
+            auto_assign_textures(&entry, &texture_names, &default_texture_suffix_rules())
+        );
+    }
+
+    fn modl_entry(mesh_object_name: &str, material_label: &str) -> ModlEntryData {
+        ModlEntryData {
+            mesh_object_name: mesh_object_name.to_owned(),
+            mesh_object_subindex: 0,
+            material_label: material_label.to_owned(),
+        }
+    }
+
+    #[test]
+    fn used_material_labels_eye_variants() {
+        let modl_entries = vec![modl_entry("a", "EyeL"), modl_entry("b", "skin")];
+        assert_eq!(
+            BTreeSet::from(["EyeL", "EyeLD", "EyeLG", "EyeLL", "skin"].map(|l| l.to_owned())),
+            used_material_labels(&modl_entries, &default_material_variant_rules())
+        );
+    }
+
+    #[test]
+    fn material_usage_meshes_anims_variants() {
+        let modl_entries = vec![
+            modl_entry("eye_l", "EyeL1"),
+            modl_entry("body", "skin"),
+            modl_entry("arm", "skin"),
+        ];
+        let anims = vec![(
+            "model.nuanmb".to_owned(),
+            Some(AnimData {
+                major_version: 2,
+                minor_version: 0,
+                final_frame_index: 0.0,
+                groups: vec![GroupData {
+                    group_type: GroupType::Material,
+                    nodes: vec![NodeData {
+                        name: "skin".to_owned(),
+                        tracks: vec![TrackData {
+                            name: "CustomVector8".to_owned(),
+                            compensate_scale: false,
+                            transform_flags: Default::default(),
+                            values: TrackValues::Vector4(vec![Vector4::new(1.0, 1.0, 1.0, 1.0)]),
+                        }],
+                    }],
+                }],
+            }),
+        )];
+        let rules = default_material_variant_rules();
+
+        assert_eq!(
+            MaterialUsage {
+                mesh_objects: vec![("body".to_owned(), 0), ("arm".to_owned(), 0)],
+                anim_tracks: vec![("model.nuanmb".to_owned(), "CustomVector8".to_owned())],
+                variant_of: None,
+            },
+            material_usage("skin", &modl_entries, &anims, &rules)
+        );
+
+        let usage = material_usage("EyeLG1", &modl_entries, &anims, &rules);
+        assert_eq!(Some("EyeL1".to_owned()), usage.variant_of);
+        assert!(!usage.is_unused());
+
+        assert!(material_usage("EyeRG1", &modl_entries, &anims, &rules).is_unused());
+    }
+
+    #[test]
+    fn rename_eye_material_variants() {
+        let mut entries: Vec<_> = ["EyeR", "EyeLD", "EyeLG", "EyeLL", "EyeRL", "skin"]
+            .into_iter()
+            .map(|label| MatlEntryData {
+                material_label: label.to_owned(),
+                ..default_material()
+            })
+            .collect();
+
+        // The material itself has already been renamed from EyeL to EyeR.
+        let rules = default_material_variant_rules();
+        assert!(!rename_material_variants(
+            &mut entries,
+            "skin",
+            "skin2",
+            &rules
+        ));
+        assert!(rename_material_variants(
+            &mut entries,
+            "EyeL",
+            "EyeR",
+            &rules
+        ));
+
+        // EyeRL already exists, so EyeLL is not renamed.
+        assert_eq!(
+            vec!["EyeR", "EyeRD", "EyeRG", "EyeLL", "EyeRL", "skin"],
+            entries
+                .iter()
+                .map(|e| e.material_label.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
 }
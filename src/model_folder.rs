@@ -2,7 +2,7 @@ use std::path::{Path, PathBuf};
 
 use ssbh_wgpu::{ModelFolder, SharedRenderData, swing::SwingPrc};
 
-use crate::{Thumbnail, validation::ModelFolderValidationErrors};
+use crate::{Thumbnail, preferences::ModelBudgets, validation::ModelFolderValidationErrors};
 
 pub struct ModelFolderState {
     pub folder_path: PathBuf,
@@ -32,7 +32,7 @@ impl ModelFolderState {
         }
     }
 
-    pub fn validate(&mut self, shared_data: &SharedRenderData) {
+    pub fn validate(&mut self, shared_data: &SharedRenderData, budgets: &ModelBudgets) {
         self.validation = ModelFolderValidationErrors::from_model(
             &self.model,
             shared_data.database(),
@@ -40,6 +40,7 @@ impl ModelFolderState {
                 .default_textures()
                 .iter()
                 .map(|(f, _, d)| (f, d.into())),
+            budgets,
         );
     }
 
@@ -54,6 +55,7 @@ impl ModelFolderState {
     pub fn reload(&mut self) {
         // Make sure the ModelFolder is updated first.
         self.model = ModelFolder::load_folder(&self.folder_path);
+        self.swing_prc = SwingPrc::from_file(self.folder_path.join("swing.prc"));
         self.changed = FileChanged::from_model(&self.model);
     }
 }
@@ -69,6 +71,8 @@ pub struct FileChanged {
     pub anims: Vec<bool>,
     pub hlpbs: Vec<bool>,
     pub nutexbs: Vec<bool>,
+    pub xmbs: Vec<bool>,
+    pub swing_prc: bool,
 }
 
 impl FileChanged {
@@ -83,6 +87,8 @@ impl FileChanged {
             anims: vec![false; model.anims.len()],
             hlpbs: vec![false; model.hlpbs.len()],
             nutexbs: vec![false; model.nutexbs.len()],
+            xmbs: vec![false; model.xmbs.len()],
+            swing_prc: false,
         }
     }
 }
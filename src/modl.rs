@@ -0,0 +1,377 @@
+use log::error;
+use serde::{Deserialize, Serialize};
+use ssbh_data::{modl_data::ModlEntryData, prelude::*};
+use std::path::Path;
+
+/// Assign `material_label` to mesh objects with names matching `pattern`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct MaterialAssignmentRule {
+    /// A case-insensitive pattern where `*` matches any characters and `?` matches one character.
+    pub pattern: String,
+    /// Only match mesh objects with this subindex if present.
+    #[serde(default)]
+    pub subindex: Option<u64>,
+    pub material_label: String,
+}
+
+impl MaterialAssignmentRule {
+    pub fn is_match(&self, entry: &ModlEntryData) -> bool {
+        !self.pattern.is_empty()
+            && self
+                .subindex
+                .is_none_or(|subindex| subindex == entry.mesh_object_subindex)
+            && glob_match(&self.pattern, &entry.mesh_object_name)
+    }
+}
+
+/// A change to a modl entry from [assign_materials].
+#[derive(Debug, PartialEq, Clone)]
+pub struct MaterialAssignment {
+    pub entry_index: usize,
+    pub old_material: String,
+    pub new_material: String,
+}
+
+/// Find new material labels for `entries` using the first matching rule for each entry.
+/// Entries that already use the matching rule's material are skipped.
+pub fn assign_materials(
+    entries: &[ModlEntryData],
+    rules: &[MaterialAssignmentRule],
+) -> Vec<MaterialAssignment> {
+    entries
+        .iter()
+        .enumerate()
+        .filter_map(|(entry_index, entry)| {
+            let rule = rules.iter().find(|r| r.is_match(entry))?;
+            (rule.material_label != entry.material_label).then(|| MaterialAssignment {
+                entry_index,
+                old_material: entry.material_label.clone(),
+                new_material: rule.material_label.clone(),
+            })
+        })
+        .collect()
+}
+
+pub fn load_material_assignment_rules<P: AsRef<Path>>(
+    path: P,
+) -> Option<Vec<MaterialAssignmentRule>> {
+    std::fs::read(path.as_ref())
+        .and_then(|data| Ok(serde_json::from_slice(&data)?))
+        .map_err(|e| {
+            error!(
+                "Failed to load material assignment rules from {:?}: {}",
+                path.as_ref(),
+                e
+            )
+        })
+        .ok()
+}
+
+pub fn save_material_assignment_rules<P: AsRef<Path>>(rules: &[MaterialAssignmentRule], path: P) {
+    match serde_json::to_string_pretty(rules) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(path.as_ref(), json) {
+                error!(
+                    "Failed to save material assignment rules to {:?}: {}",
+                    path.as_ref(),
+                    e
+                );
+            }
+        }
+        Err(e) => error!("Failed to convert material assignment rules to JSON: {e}"),
+    }
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<_> = pattern.to_lowercase().chars().collect();
+    let text: Vec<_> = text.to_lowercase().chars().collect();
+
+    // Backtrack to the most recent '*' on a mismatch.
+    let mut p = 0;
+    let mut t = 0;
+    let mut star = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Create a new modl with one entry for each object in `mesh`.
+/// Materials are assigned by matching mesh object names with the material labels
+/// in the matl from `matls` used for the modl's material file name.
+pub fn new_modl(
+    mesh: &MeshData,
+    skel_file_names: &[&str],
+    matls: &[(String, Option<MatlData>)],
+) -> ModlData {
+    let matl_file_names: Vec<_> = matls.iter().map(|(f, _)| f.as_str()).collect();
+    let matl_file_name = file_name_or_default(&matl_file_names, "model.numatb");
+
+    let material_labels: Vec<_> = matls
+        .iter()
+        .find(|(f, _)| f == &matl_file_name)
+        .and_then(|(_, m)| m.as_ref())
+        .map(|m| {
+            m.entries
+                .iter()
+                .map(|e| e.material_label.as_str())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ModlData {
+        major_version: 1,
+        minor_version: 7,
+        model_name: "model".to_owned(),
+        skeleton_file_name: file_name_or_default(skel_file_names, "model.nusktb"),
+        material_file_names: vec![matl_file_name],
+        animation_file_name: None,
+        mesh_file_name: "model.numshb".to_owned(),
+        entries: mesh
+            .objects
+            .iter()
+            .map(|o| ModlEntryData {
+                mesh_object_name: o.name.clone(),
+                mesh_object_subindex: o.subindex,
+                material_label: match_material_label(&o.name, &material_labels),
+            })
+            .collect(),
+    }
+}
+
+/// Prefer the default file name if present and otherwise use the first file.
+fn file_name_or_default(file_names: &[&str], default: &str) -> String {
+    if file_names.contains(&default) {
+        default.to_owned()
+    } else {
+        file_names.first().unwrap_or(&default).to_string()
+    }
+}
+
+/// Find the material label that best matches `mesh_object_name`.
+pub fn match_material_label(mesh_object_name: &str, material_labels: &[&str]) -> String {
+    let mesh_name = normalized_name(mesh_object_name);
+
+    material_labels
+        .iter()
+        .find(|label| normalized_name(label) == mesh_name)
+        .or_else(|| {
+            // Prefer the longest label to avoid matching short labels like "a" everywhere.
+            material_labels
+                .iter()
+                .filter(|label| {
+                    let label = normalized_name(label);
+                    !label.is_empty() && (mesh_name.contains(&label) || label.contains(&mesh_name))
+                })
+                .max_by_key(|label| label.len())
+        })
+        // Pick an arbitrary material to make the mesh visible in the viewport.
+        .or_else(|| material_labels.first())
+        .map(|label| label.to_string())
+        .unwrap_or_else(|| String::from("PLACEHOLDER"))
+}
+
+fn normalized_name(name: &str) -> String {
+    // Mesh names often have suffixes like "_VIS_O_OBJShape" or "Shape".
+    let name = name.to_lowercase();
+    let name = match name.find("_vis") {
+        Some(index) => &name[..index],
+        None => name.as_str(),
+    };
+    let name = name.strip_suffix("shape").unwrap_or(name);
+    name.trim_matches('_').to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::default_material;
+    use ssbh_data::mesh_data::MeshObjectData;
+
+    fn mesh_object(name: &str, subindex: u64) -> MeshObjectData {
+        MeshObjectData {
+            name: name.to_owned(),
+            subindex,
+            ..Default::default()
+        }
+    }
+
+    fn entry(name: &str, subindex: u64, material: &str) -> ModlEntryData {
+        ModlEntryData {
+            mesh_object_name: name.to_owned(),
+            mesh_object_subindex: subindex,
+            material_label: material.to_owned(),
+        }
+    }
+
+    fn rule(pattern: &str, subindex: Option<u64>, material: &str) -> MaterialAssignmentRule {
+        MaterialAssignmentRule {
+            pattern: pattern.to_owned(),
+            subindex,
+            material_label: material.to_owned(),
+        }
+    }
+
+    #[test]
+    fn glob_patterns() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*", "body"));
+        assert!(glob_match("Eye*", "EyeL_VIS_O_OBJShape"));
+        assert!(glob_match("eye?_*", "EyeL_VIS_O_OBJShape"));
+        assert!(glob_match("*hair*", "alp_mario_002_hairShape"));
+        assert!(glob_match("body", "BODY"));
+        assert!(!glob_match("body", "body1"));
+        assert!(!glob_match("eye?", "eye"));
+        assert!(!glob_match("*hair", "hairShape"));
+        assert!(!glob_match("", "body"));
+    }
+
+    #[test]
+    fn assign_materials_first_matching_rule() {
+        let entries = vec![
+            entry("EyeL_VIS_O_OBJShape", 0, "a"),
+            entry("EyeR_VIS_O_OBJShape", 0, "eye"),
+            entry("body", 0, "a"),
+            entry("body", 1, "a"),
+            entry("hair", 0, "a"),
+        ];
+        let rules = vec![
+            rule("Eye*", None, "eye"),
+            rule("body", Some(1), "body_alt"),
+            rule("body", None, "body"),
+            rule("", None, "ignored"),
+        ];
+
+        assert_eq!(
+            vec![
+                MaterialAssignment {
+                    entry_index: 0,
+                    old_material: "a".to_owned(),
+                    new_material: "eye".to_owned()
+                },
+                MaterialAssignment {
+                    entry_index: 2,
+                    old_material: "a".to_owned(),
+                    new_material: "body".to_owned()
+                },
+                MaterialAssignment {
+                    entry_index: 3,
+                    old_material: "a".to_owned(),
+                    new_material: "body_alt".to_owned()
+                }
+            ],
+            assign_materials(&entries, &rules)
+        );
+    }
+
+    #[test]
+    fn material_assignment_rules_json() {
+        let rules: Vec<MaterialAssignmentRule> = serde_json::from_str(
+            r#"[
+                { "pattern": "Eye*", "material_label": "eye" },
+                { "pattern": "body", "subindex": 1, "material_label": "body_alt" }
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            vec![rule("Eye*", None, "eye"), rule("body", Some(1), "body_alt")],
+            rules
+        );
+    }
+
+    #[test]
+    fn match_material_labels() {
+        let labels = ["alp_mario_002", "EyeL", "EyeR", "a"];
+        assert_eq!("EyeL", match_material_label("EyeL_VIS_O_OBJShape", &labels));
+        assert_eq!("EyeR", match_material_label("eyer", &labels));
+        assert_eq!(
+            "alp_mario_002",
+            match_material_label("alp_mario_002_hairShape", &labels)
+        );
+        assert_eq!("alp_mario_002", match_material_label("body", &labels));
+        assert_eq!("PLACEHOLDER", match_material_label("body", &[]));
+    }
+
+    #[test]
+    fn new_modl_entries_and_files() {
+        let mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![
+                mesh_object("EyeL_VIS_O_OBJShape", 0),
+                mesh_object("body", 0),
+                mesh_object("body", 1),
+            ],
+        };
+        let matl = |labels: &[&str]| MatlData {
+            major_version: 1,
+            minor_version: 6,
+            entries: labels
+                .iter()
+                .map(|label| ssbh_data::matl_data::MatlEntryData {
+                    material_label: label.to_string(),
+                    ..default_material()
+                })
+                .collect(),
+        };
+
+        let modl = new_modl(
+            &mesh,
+            &["model.nusktb"],
+            &[
+                ("alt.numatb".to_owned(), Some(matl(&["alt_mat"]))),
+                ("model.numatb".to_owned(), Some(matl(&["body_mat", "EyeL"]))),
+            ],
+        );
+        assert_eq!("model", modl.model_name);
+        assert_eq!("model.nusktb", modl.skeleton_file_name);
+        assert_eq!(vec!["model.numatb".to_owned()], modl.material_file_names);
+        assert_eq!("model.numshb", modl.mesh_file_name);
+        assert_eq!(
+            vec![
+                ModlEntryData {
+                    mesh_object_name: "EyeL_VIS_O_OBJShape".to_owned(),
+                    mesh_object_subindex: 0,
+                    material_label: "EyeL".to_owned()
+                },
+                ModlEntryData {
+                    mesh_object_name: "body".to_owned(),
+                    mesh_object_subindex: 0,
+                    material_label: "body_mat".to_owned()
+                },
+                ModlEntryData {
+                    mesh_object_name: "body".to_owned(),
+                    mesh_object_subindex: 1,
+                    material_label: "body_mat".to_owned()
+                }
+            ],
+            modl.entries
+        );
+
+        // Labels should come from the same matl used for the material file name.
+        let modl = new_modl(
+            &mesh,
+            &[],
+            &[("alt.numatb".to_owned(), Some(matl(&["alt_mat"])))],
+        );
+        assert_eq!("model.nusktb", modl.skeleton_file_name);
+        assert_eq!(vec!["alt.numatb".to_owned()], modl.material_file_names);
+        assert_eq!("alt_mat", modl.entries[0].material_label);
+
+        let modl = new_modl(&mesh, &[], &[("alt.numatb".to_owned(), None)]);
+        assert_eq!(vec!["alt.numatb".to_owned()], modl.material_file_names);
+        assert_eq!("PLACEHOLDER", modl.entries[0].material_label);
+    }
+}
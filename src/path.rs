@@ -20,6 +20,14 @@ pub fn presets_file() -> PathBuf {
     PROJECT_DIR.data_local_dir().join("presets.json")
 }
 
+pub fn preset_library_file() -> PathBuf {
+    PROJECT_DIR.data_local_dir().join("preset_library.json")
+}
+
+pub fn material_variants_file() -> PathBuf {
+    PROJECT_DIR.data_local_dir().join("material_variants.json")
+}
+
 pub fn preferences_file() -> PathBuf {
     PROJECT_DIR.data_local_dir().join("preferences.json")
 }
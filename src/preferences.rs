@@ -1,4 +1,9 @@
-use crate::{CameraValues, path::preferences_file, widgets_dark};
+use crate::{
+    CameraValues,
+    material::{TextureSuffixRule, default_texture_suffix_rules},
+    path::preferences_file,
+    widgets_dark,
+};
 use log::error;
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumString, EnumVariantNames};
@@ -35,6 +40,19 @@ pub struct AppPreferences {
     pub graphics_backend: GraphicsBackend,
     pub scale_factor: f32,
     pub default_camera: CameraValues,
+    pub budgets: ModelBudgets,
+    pub texture_suffix_rules: Vec<TextureSuffixRule>,
+}
+
+/// Optional limits for model folders that add validation warnings when exceeded.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(default)]
+pub struct ModelBudgets {
+    pub mesh_vertex_count: Option<usize>,
+    pub mesh_bone_count: Option<usize>,
+    pub folder_vertex_count: Option<usize>,
+    pub texture_size: Option<usize>,
+    pub folder_texture_size: Option<usize>,
 }
 
 impl AppPreferences {
@@ -83,6 +101,8 @@ impl Default for AppPreferences {
             graphics_backend: GraphicsBackend::default(),
             scale_factor: 1.0,
             default_camera: CameraValues::default(),
+            budgets: ModelBudgets::default(),
+            texture_suffix_rules: default_texture_suffix_rules(),
         }
     }
 }
@@ -1,16 +1,164 @@
 use crate::material::{
-    default_texture, is_blend, is_bool, is_float, is_rasterizer, is_sampler, is_texture, is_vector,
+    default_texture, is_blend, is_bool, is_float, is_rasterizer, is_sampler, is_texture,
+    is_uv_transform, is_vector, load_material_presets,
 };
 use anyhow::anyhow;
-use ssbh_data::{Vector4, matl_data::*};
-use std::str::FromStr;
-use xmltree::{Element, XMLNode};
+use log::error;
+use serde::{Deserialize, Serialize};
+use ssbh_data::{Color4f, Vector4, matl_data::*};
+use std::{path::Path, str::FromStr};
+use strum::{Display, EnumIter, IntoEnumIterator};
+use xmltree::{Element, EmitterConfig, XMLNode};
+
+/// A material preset with additional information for organizing and searching presets.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct MaterialPreset {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub category: PresetCategory,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// The path to an optional PNG or JPEG preview image.
+    #[serde(default)]
+    pub thumbnail: Option<String>,
+    pub material: MatlEntryData,
+}
+
+impl MaterialPreset {
+    pub fn new(material: MatlEntryData) -> Self {
+        Self {
+            name: material.material_label.clone(),
+            description: String::new(),
+            category: PresetCategory::default(),
+            tags: Vec::new(),
+            thumbnail: None,
+            material,
+        }
+    }
+
+    /// Returns `true` if all of the words in `search` appear in the preset's text.
+    /// The search is not case sensitive.
+    pub fn matches_search(&self, search: &str) -> bool {
+        let text = format!(
+            "{} {} {} {} {}",
+            self.name,
+            self.description,
+            self.category,
+            self.tags.join(" "),
+            self.material.shader_label
+        )
+        .to_lowercase();
+
+        search
+            .to_lowercase()
+            .split_whitespace()
+            .all(|word| text.contains(word))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize, Display, EnumIter)]
+pub enum PresetCategory {
+    #[default]
+    Other,
+    Skin,
+    Hair,
+    Eyes,
+    Metal,
+    Cloth,
+    Glass,
+    Emission,
+}
+
+/// The file format for the user's material presets.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct PresetLibrary {
+    pub version: u32,
+    pub presets: Vec<MaterialPreset>,
+}
+
+impl PresetLibrary {
+    pub const VERSION: u32 = 1;
+}
+
+/// Load the preset library from `path`.
+/// If the library does not exist yet, migrate the presets from the older `legacy_path` JSON format.
+pub fn load_preset_library<P: AsRef<Path>>(path: P, legacy_path: P) -> Vec<MaterialPreset> {
+    match std::fs::read(path.as_ref()) {
+        Ok(bytes) => match serde_json::from_slice::<PresetLibrary>(&bytes) {
+            Ok(library) => library.presets,
+            Err(e) => {
+                error!("Failed to load presets from {:?}: {}", path.as_ref(), e);
+                Vec::new()
+            }
+        },
+        // The older presets file is left unchanged in case it's still used by older versions.
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => load_material_presets(legacy_path)
+            .into_iter()
+            .map(MaterialPreset::new)
+            .collect(),
+        Err(e) => {
+            error!("Failed to load presets from {:?}: {}", path.as_ref(), e);
+            Vec::new()
+        }
+    }
+}
+
+pub fn save_preset_library<P: AsRef<Path>>(presets: &[MaterialPreset], path: P) {
+    // TODO: Give a visual indication that the file saved?
+    match serde_json::to_string_pretty(&PresetLibrary {
+        version: PresetLibrary::VERSION,
+        presets: presets.to_vec(),
+    }) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(path.as_ref(), json) {
+                error!(
+                    "Failed to save material presets to {:?}: {}",
+                    path.as_ref(),
+                    e
+                );
+            }
+        }
+        Err(e) => error!("Failed to convert material presets to JSON: {e}"),
+    }
+}
 
 fn default_texture_param(param: ParamId) -> TextureParam {
     TextureParam::new(param, default_texture(param).to_string())
 }
 
-pub fn default_presets() -> Vec<MatlEntryData> {
+pub fn default_presets() -> Vec<MaterialPreset> {
+    default_materials()
+        .into_iter()
+        .map(|material| {
+            let (category, description) = match material.material_label.as_str() {
+                "PRM Skin Opaque" => (PresetCategory::Skin, "Skin with subsurface scattering."),
+                "PRM Anisotropic Alpha Test" => (
+                    PresetCategory::Hair,
+                    "Hair with anisotropic specular and alpha testing.",
+                ),
+                "PRM Emi Opaque" => (PresetCategory::Emission, "PRM material with glow."),
+                "Emi Nor Shadeless" | "Emi Shadeless" => (
+                    PresetCategory::Emission,
+                    "Unlit material using only the emission texture.",
+                ),
+                "Glass Angle Fade" => (
+                    PresetCategory::Glass,
+                    "Transparent glass that fades based on viewing angle.",
+                ),
+                _ => (PresetCategory::Other, ""),
+            };
+            MaterialPreset {
+                category,
+                description: description.to_owned(),
+                ..MaterialPreset::new(material)
+            }
+        })
+        .collect()
+}
+
+fn default_materials() -> Vec<MatlEntryData> {
     vec![
         MatlEntryData {
             material_label: "PRM Opaque".into(),
@@ -430,6 +578,16 @@ pub fn load_json_presets(json: &[u8]) -> anyhow::Result<Vec<MatlEntryData>> {
         .map_err(Into::into)
 }
 
+/// Convert `entries` to the ssbh_data_json matl format used by [load_json_presets].
+pub fn export_json_presets(entries: &[MatlEntryData]) -> anyhow::Result<String> {
+    serde_json::to_string_pretty(&MatlData {
+        major_version: 1,
+        minor_version: 6,
+        entries: entries.to_vec(),
+    })
+    .map_err(Into::into)
+}
+
 fn first_child(node: &Element) -> anyhow::Result<&Element> {
     node.children
         .first()
@@ -444,7 +602,24 @@ fn attribute(node: &Element, name: &str) -> anyhow::Result<String> {
         .cloned()
 }
 
+/// Materials from a Cross Mod MaterialLibrary XML file.
+#[derive(Debug, PartialEq)]
+pub struct XmlPresets {
+    pub entries: Vec<MatlEntryData>,
+    /// Fields like "material_label/Sampler0/LodBias" that could not be parsed.
+    /// Unparsed fields use the default value instead.
+    pub unparsed_fields: Vec<String>,
+}
+
 pub fn load_xml_presets(xml_text: &[u8]) -> anyhow::Result<Vec<MatlEntryData>> {
+    let presets = parse_xml_presets(xml_text)?;
+    for field in &presets.unparsed_fields {
+        error!("Failed to parse XML preset field {field}. Using the default value instead.");
+    }
+    Ok(presets.entries)
+}
+
+pub fn parse_xml_presets(xml_text: &[u8]) -> anyhow::Result<XmlPresets> {
     let element = Element::parse(xml_text)?;
     if element.name != "MaterialLibrary" {
         return Err(anyhow!(
@@ -453,86 +628,776 @@ pub fn load_xml_presets(xml_text: &[u8]) -> anyhow::Result<Vec<MatlEntryData>> {
         ));
     }
 
-    element
+    let mut unparsed_fields = Vec::new();
+    let entries = element
         .children
         .iter()
         .filter_map(XMLNode::as_element)
-        .map(|node| {
-            let mut blend_states = Vec::new();
-            let mut floats = Vec::new();
-            let mut booleans = Vec::new();
-            let mut vectors = Vec::new();
-            let mut rasterizer_states = Vec::new();
-            let mut samplers = Vec::new();
-            let mut textures = Vec::new();
-
-            for param_node in node.children.iter().filter_map(XMLNode::as_element) {
-                let param_id = ParamId::from_str(&attribute(param_node, "name")?)?;
-
-                if is_blend(param_id) {
-                    let child_node = first_child(param_node)?;
-                    let data = BlendStateData {
-                        source_color: parse_xml_text(child_node, 0)?,
-                        destination_color: parse_xml_text(child_node, 2)?,
-                        alpha_sample_to_coverage: parse_xml_text::<usize>(child_node, 6)? != 0,
-                        ..Default::default()
-                    };
-                    blend_states.push(ParamData::new(param_id, data))
-                } else if is_bool(param_id) {
-                    let data = parse_xml_text(param_node, 0)?;
-                    booleans.push(ParamData::new(param_id, data));
-                } else if is_float(param_id) {
-                    let data = parse_xml_text(param_node, 0)?;
-                    floats.push(ParamData::new(param_id, data));
-                } else if is_vector(param_id) {
-                    let child_node = first_child(param_node)?;
-                    let x = parse_xml_text(child_node, 0)?;
-                    let y = parse_xml_text(child_node, 1)?;
-                    let z = parse_xml_text(child_node, 2)?;
-                    let w = parse_xml_text(child_node, 3)?;
-                    let data = Vector4::new(x, y, z, w);
-                    vectors.push(ParamData::new(param_id, data));
-                } else if is_rasterizer(param_id) {
-                    let child_node = first_child(param_node)?;
-                    let data = RasterizerStateData {
-                        fill_mode: parse_xml_text(child_node, 0)?,
-                        cull_mode: parse_xml_text(child_node, 1)?,
-                        depth_bias: parse_xml_text(child_node, 2)?,
-                    };
-                    rasterizer_states.push(ParamData::new(param_id, data));
-                } else if is_sampler(param_id) {
-                    samplers.push(ParamData::new(param_id, Default::default()));
-                } else if is_texture(param_id) {
-                    textures.push(default_texture_param(param_id));
+        .map(|node| parse_xml_material(node, &mut unparsed_fields))
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok(XmlPresets {
+        entries,
+        unparsed_fields,
+    })
+}
+
+fn parse_xml_material(
+    node: &Element,
+    unparsed_fields: &mut Vec<String>,
+) -> anyhow::Result<MatlEntryData> {
+    let mut entry = MatlEntryData {
+        material_label: attribute(node, "materialLabel")?,
+        shader_label: attribute(node, "shaderLabel")?,
+        blend_states: Vec::new(),
+        floats: Vec::new(),
+        booleans: Vec::new(),
+        vectors: Vec::new(),
+        rasterizer_states: Vec::new(),
+        samplers: Vec::new(),
+        textures: Vec::new(),
+        uv_transforms: Vec::new(),
+    };
+
+    for param_node in node.children.iter().filter_map(XMLNode::as_element) {
+        let name = attribute(param_node, "name")?;
+        let Ok(param_id) = ParamId::from_str(&name) else {
+            unparsed_fields.push(format!("{}/{name}", entry.material_label));
+            continue;
+        };
+
+        let mut fields = XmlFields {
+            path: format!("{}/{name}", entry.material_label),
+            unparsed_fields,
+        };
+        // Cross Mod uses a single child node for structs like BlendState or Vector4.
+        let child_node = first_child(param_node).ok();
+
+        if is_blend(param_id) {
+            let mut data = BlendStateData::default();
+            fields.field(
+                &mut data.source_color,
+                child_node,
+                "SourceColor",
+                0,
+                parse_enum,
+            );
+            fields.field(
+                &mut data.color_operation,
+                child_node,
+                "ColorOperation",
+                1,
+                parse_enum,
+            );
+            fields.field(
+                &mut data.destination_color,
+                child_node,
+                "DestinationColor",
+                2,
+                parse_enum,
+            );
+            fields.field(
+                &mut data.source_alpha,
+                child_node,
+                "SourceAlpha",
+                3,
+                parse_enum,
+            );
+            fields.field(
+                &mut data.alpha_operation,
+                child_node,
+                "AlphaOperation",
+                4,
+                parse_enum,
+            );
+            fields.field(
+                &mut data.destination_alpha,
+                child_node,
+                "DestinationAlpha",
+                5,
+                parse_enum,
+            );
+            fields.field(
+                &mut data.alpha_sample_to_coverage,
+                child_node,
+                "AlphaSampleToCoverage",
+                6,
+                parse_bool,
+            );
+            entry.blend_states.push(ParamData::new(param_id, data));
+        } else if is_bool(param_id) {
+            let mut data = false;
+            fields.value(&mut data, param_node, parse_bool);
+            entry.booleans.push(ParamData::new(param_id, data));
+        } else if is_float(param_id) {
+            let mut data = 0.0;
+            fields.value(&mut data, param_node, parse_value);
+            entry.floats.push(ParamData::new(param_id, data));
+        } else if is_vector(param_id) {
+            let mut data = Vector4::new(0.0, 0.0, 0.0, 0.0);
+            fields.vector4(&mut data, child_node, "");
+            entry.vectors.push(ParamData::new(param_id, data));
+        } else if is_rasterizer(param_id) {
+            let mut data = RasterizerStateData::default();
+            fields.field(&mut data.fill_mode, child_node, "FillMode", 0, parse_enum);
+            fields.field(&mut data.cull_mode, child_node, "CullMode", 1, parse_enum);
+            fields.field(
+                &mut data.depth_bias,
+                child_node,
+                "DepthBias",
+                2,
+                parse_value,
+            );
+            entry.rasterizer_states.push(ParamData::new(param_id, data));
+        } else if is_sampler(param_id) {
+            // Fall back to the field order from Cross Mod for older files.
+            let mut data = SamplerData::default();
+            fields.field(&mut data.wraps, child_node, "WrapS", 0, parse_enum);
+            fields.field(&mut data.wrapt, child_node, "WrapT", 1, parse_enum);
+            fields.field(&mut data.wrapr, child_node, "WrapR", 2, parse_enum);
+            fields.field(&mut data.min_filter, child_node, "MinFilter", 3, parse_enum);
+            fields.field(&mut data.mag_filter, child_node, "MagFilter", 4, parse_enum);
+
+            let border_node = child_node.and_then(|n| xml_child(n, "BorderColor", 6));
+            let c = &data.border_color;
+            let mut border_color = Vector4::new(c.r, c.g, c.b, c.a);
+            fields.vector4(&mut border_color, border_node, "BorderColor/");
+            data.border_color = Color4f {
+                r: border_color.x,
+                g: border_color.y,
+                b: border_color.z,
+                a: border_color.w,
+            };
+
+            fields.field(&mut data.lod_bias, child_node, "LodBias", 9, parse_value);
+            fields.field(
+                &mut data.max_anisotropy,
+                child_node,
+                "MaxAnisotropy",
+                10,
+                parse_anisotropy,
+            );
+            entry.samplers.push(ParamData::new(param_id, data));
+        } else if is_texture(param_id) {
+            let mut data = default_texture(param_id).to_string();
+            fields.value(&mut data, param_node, |text| {
+                (!text.is_empty()).then(|| text.to_string())
+            });
+            entry.textures.push(ParamData::new(param_id, data));
+        } else if is_uv_transform(param_id) {
+            let mut data = UvTransform {
+                scale_u: 1.0,
+                scale_v: 1.0,
+                rotation: 0.0,
+                translate_u: 0.0,
+                translate_v: 0.0,
+            };
+            fields.field(&mut data.scale_u, child_node, "ScaleU", 0, parse_value);
+            fields.field(&mut data.scale_v, child_node, "ScaleV", 1, parse_value);
+            fields.field(&mut data.rotation, child_node, "Rotation", 2, parse_value);
+            fields.field(
+                &mut data.translate_u,
+                child_node,
+                "TranslateU",
+                3,
+                parse_value,
+            );
+            fields.field(
+                &mut data.translate_v,
+                child_node,
+                "TranslateV",
+                4,
+                parse_value,
+            );
+            entry.uv_transforms.push(ParamData::new(param_id, data));
+        } else {
+            fields.unparsed_fields.push(fields.path);
+        }
+    }
+
+    Ok(entry)
+}
+
+/// Parses the fields for a single param and records any fields that could not be parsed.
+struct XmlFields<'a> {
+    path: String,
+    unparsed_fields: &'a mut Vec<String>,
+}
+
+impl XmlFields<'_> {
+    /// Parse the inner text of the param node itself.
+    fn value<T>(&mut self, value: &mut T, node: &Element, parse: impl Fn(&str) -> Option<T>) {
+        match inner_text(node).and_then(|text| parse(text.trim())) {
+            Some(v) => *value = v,
+            None => self.unparsed_fields.push(self.path.clone()),
+        }
+    }
+
+    /// Parse the child of `node` with the given `name` or child `index` if not found.
+    fn field<T>(
+        &mut self,
+        value: &mut T,
+        node: Option<&Element>,
+        name: &str,
+        index: usize,
+        parse: impl Fn(&str) -> Option<T>,
+    ) {
+        match node
+            .and_then(|n| xml_child(n, name, index))
+            .and_then(inner_text)
+            .and_then(|text| parse(text.trim()))
+        {
+            Some(v) => *value = v,
+            None => self.unparsed_fields.push(format!("{}/{name}", self.path)),
+        }
+    }
+
+    fn vector4(&mut self, value: &mut Vector4, node: Option<&Element>, prefix: &str) {
+        // Colors may use RGBA instead of XYZW.
+        self.field(&mut value.x, node, &format!("{prefix}X"), 0, parse_value);
+        self.field(&mut value.y, node, &format!("{prefix}Y"), 1, parse_value);
+        self.field(&mut value.z, node, &format!("{prefix}Z"), 2, parse_value);
+        self.field(&mut value.w, node, &format!("{prefix}W"), 3, parse_value);
+    }
+}
+
+fn xml_child<'a>(node: &'a Element, name: &str, index: usize) -> Option<&'a Element> {
+    // The name includes a prefix for nested fields like "BorderColor/X".
+    let name = name.rsplit('/').next().unwrap_or(name);
+    node.get_child(name)
+        .or_else(|| node.children.get(index).and_then(XMLNode::as_element))
+}
+
+fn inner_text(node: &Element) -> Option<String> {
+    // Values may be nested inside additional nodes like <MatlString>.
+    node.get_text()
+        .map(|text| text.to_string())
+        .or_else(|| first_child(node).ok().and_then(inner_text))
+}
+
+fn parse_value<T: FromStr>(text: &str) -> Option<T> {
+    text.parse().ok()
+}
+
+fn parse_bool(text: &str) -> Option<bool> {
+    // Cross Mod uses integers for some boolean fields.
+    match text {
+        "1" => Some(true),
+        "0" => Some(false),
+        _ => text.parse().ok(),
+    }
+}
+
+fn parse_enum<T: FromStr + MatlEnumValue>(text: &str) -> Option<T> {
+    // Cross Mod uses the integer values from the matl format for some enum fields.
+    text.parse().ok().or_else(|| {
+        let value: u32 = text.parse().ok()?;
+        T::iter().find(|v| v.value() == value)
+    })
+}
+
+/// Enums with the same integer values as the matl file format.
+trait MatlEnumValue: Copy + IntoEnumIterator {
+    fn value(self) -> u32;
+}
+
+macro_rules! matl_enum_value {
+    ($($ty:ty),*) => {
+        $(
+            impl MatlEnumValue for $ty {
+                fn value(self) -> u32 {
+                    self as u32
                 }
             }
+        )*
+    };
+}
 
-            Ok(MatlEntryData {
-                material_label: attribute(node, "materialLabel")?,
-                shader_label: attribute(node, "shaderLabel")?,
-                blend_states,
-                floats,
-                booleans,
-                vectors,
-                rasterizer_states,
-                samplers,
-                textures,
-                uv_transforms: Vec::new(),
-            })
-        })
-        .collect()
+matl_enum_value!(
+    BlendFactor,
+    BlendOperation,
+    FillMode,
+    CullMode,
+    WrapMode,
+    MinFilter,
+    MagFilter
+);
+
+fn parse_anisotropy(text: &str) -> Option<MaxAnisotropy> {
+    match text {
+        "1" => Some(MaxAnisotropy::One),
+        "2" => Some(MaxAnisotropy::Two),
+        "4" => Some(MaxAnisotropy::Four),
+        "8" => Some(MaxAnisotropy::Eight),
+        "16" => Some(MaxAnisotropy::Sixteen),
+        _ => text.parse().ok(),
+    }
 }
 
-fn parse_xml_text<T: FromStr>(node: &Element, index: usize) -> anyhow::Result<T>
-where
-    <T as FromStr>::Err: std::error::Error + Send + Sync + 'static,
-{
-    node.children
-        .get(index)
-        .and_then(XMLNode::as_element)
-        .ok_or_else(|| anyhow!("Node {} is missing child at index {}", node.name, index))?
-        .get_text()
-        .ok_or_else(|| anyhow!("Node {} has no inner text.", node.name))?
-        .parse()
-        .map_err(Into::into)
+/// Convert `entries` to the Cross Mod MaterialLibrary XML format used by [load_xml_presets].
+pub fn export_xml_presets(entries: &[MatlEntryData]) -> anyhow::Result<String> {
+    let mut library = Element::new("MaterialLibrary");
+    for entry in entries {
+        let mut material = Element::new("material");
+        material
+            .attributes
+            .insert("materialLabel".to_owned(), entry.material_label.clone());
+        material
+            .attributes
+            .insert("shaderLabel".to_owned(), entry.shader_label.clone());
+
+        // Match the child order expected by the importer for each param type.
+        // Enums use the integer values from the matl format like Cross Mod.
+        for p in &entry.blend_states {
+            let data = &p.data;
+            let blend_state = xml_element(
+                "BlendState",
+                vec![
+                    xml_text("SourceColor", data.source_color.value()),
+                    xml_text("ColorOperation", data.color_operation.value()),
+                    xml_text("DestinationColor", data.destination_color.value()),
+                    xml_text("SourceAlpha", data.source_alpha.value()),
+                    xml_text("AlphaOperation", data.alpha_operation.value()),
+                    xml_text("DestinationAlpha", data.destination_alpha.value()),
+                    xml_text(
+                        "AlphaSampleToCoverage",
+                        usize::from(data.alpha_sample_to_coverage),
+                    ),
+                ],
+            );
+            material.children.push(xml_param(p.param_id, blend_state));
+        }
+        for p in &entry.floats {
+            let float = xml_text("float", p.data);
+            material.children.push(xml_param(p.param_id, float));
+        }
+        for p in &entry.booleans {
+            let bool = xml_text("bool", p.data);
+            material.children.push(xml_param(p.param_id, bool));
+        }
+        for p in &entry.vectors {
+            let vector = xml_vector4("Vector4", &p.data);
+            material.children.push(xml_param(p.param_id, vector));
+        }
+        for p in &entry.rasterizer_states {
+            let data = &p.data;
+            let rasterizer_state = xml_element(
+                "RasterizerState",
+                vec![
+                    xml_text("FillMode", data.fill_mode.value()),
+                    xml_text("CullMode", data.cull_mode.value()),
+                    xml_text("DepthBias", data.depth_bias),
+                ],
+            );
+            material
+                .children
+                .push(xml_param(p.param_id, rasterizer_state));
+        }
+        for p in &entry.samplers {
+            let data = &p.data;
+            let color = &data.border_color;
+            let sampler = xml_element(
+                "Sampler",
+                vec![
+                    xml_text("WrapS", data.wraps.value()),
+                    xml_text("WrapT", data.wrapt.value()),
+                    xml_text("WrapR", data.wrapr.value()),
+                    xml_text("MinFilter", data.min_filter.value()),
+                    xml_text("MagFilter", data.mag_filter.value()),
+                    xml_vector4(
+                        "BorderColor",
+                        &Vector4::new(color.r, color.g, color.b, color.a),
+                    ),
+                    xml_text("LodBias", data.lod_bias),
+                    xml_text("MaxAnisotropy", data.max_anisotropy),
+                ],
+            );
+            material.children.push(xml_param(p.param_id, sampler));
+        }
+        for p in &entry.textures {
+            let texture = xml_text("MatlString", &p.data);
+            material.children.push(xml_param(p.param_id, texture));
+        }
+        for p in &entry.uv_transforms {
+            let data = &p.data;
+            let uv_transform = xml_element(
+                "UvTransform",
+                vec![
+                    xml_text("ScaleU", data.scale_u),
+                    xml_text("ScaleV", data.scale_v),
+                    xml_text("Rotation", data.rotation),
+                    xml_text("TranslateU", data.translate_u),
+                    xml_text("TranslateV", data.translate_v),
+                ],
+            );
+            material.children.push(xml_param(p.param_id, uv_transform));
+        }
+
+        library.children.push(XMLNode::Element(material));
+    }
+
+    let mut xml = Vec::new();
+    library.write_with_config(&mut xml, EmitterConfig::new().perform_indent(true))?;
+    String::from_utf8(xml).map_err(Into::into)
+}
+
+fn xml_element(name: &str, children: Vec<Element>) -> Element {
+    let mut element = Element::new(name);
+    element.children = children.into_iter().map(XMLNode::Element).collect();
+    element
+}
+
+fn xml_text<T: ToString>(name: &str, value: T) -> Element {
+    let mut element = Element::new(name);
+    element.children.push(XMLNode::Text(value.to_string()));
+    element
+}
+
+fn xml_vector4(name: &str, value: &Vector4) -> Element {
+    xml_element(
+        name,
+        vec![
+            xml_text("X", value.x),
+            xml_text("Y", value.y),
+            xml_text("Z", value.z),
+            xml_text("W", value.w),
+        ],
+    )
+}
+
+fn xml_param(param_id: ParamId, value: Element) -> XMLNode {
+    let mut param = xml_element("param", vec![value]);
+    param
+        .attributes
+        .insert("name".to_owned(), param_id.to_string());
+    XMLNode::Element(param)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::default_material;
+
+    #[test]
+    fn preset_matches_search() {
+        let preset = MaterialPreset {
+            name: "Shiny Metal".to_owned(),
+            description: "Polished armor".to_owned(),
+            category: PresetCategory::Metal,
+            tags: vec!["armor".to_owned(), "prm".to_owned()],
+            thumbnail: None,
+            material: default_materials()[0].clone(),
+        };
+
+        assert!(preset.matches_search(""));
+        assert!(preset.matches_search("metal"));
+        assert!(preset.matches_search("ARMOR shiny"));
+        assert!(preset.matches_search("SFX_PBS_0100000008008269"));
+        assert!(!preset.matches_search("skin"));
+        assert!(!preset.matches_search("metal skin"));
+    }
+
+    #[test]
+    fn preset_library_optional_fields() {
+        let material = serde_json::to_value(&default_materials()[0]).unwrap();
+        let json = serde_json::json!({
+            "version": 1,
+            "presets": [{ "name": "a", "material": material }]
+        });
+
+        assert_eq!(
+            PresetLibrary {
+                version: 1,
+                presets: vec![MaterialPreset {
+                    name: "a".to_owned(),
+                    description: String::new(),
+                    category: PresetCategory::Other,
+                    tags: Vec::new(),
+                    thumbnail: None,
+                    material: default_materials()[0].clone(),
+                }]
+            },
+            serde_json::from_value(json).unwrap()
+        );
+    }
+
+    #[test]
+    fn json_presets_round_trip() {
+        let entries = default_materials();
+        let json = export_json_presets(&entries).unwrap();
+        assert_eq!(entries, load_json_presets(json.as_bytes()).unwrap());
+    }
+
+    #[test]
+    fn xml_presets_round_trip() {
+        let entries = vec![MatlEntryData {
+            material_label: "a".to_owned(),
+            shader_label: "SFX_PBS_0100000008008269_opaque".to_owned(),
+            blend_states: vec![ParamData::new(
+                ParamId::BlendState0,
+                BlendStateData {
+                    source_color: BlendFactor::SourceAlpha,
+                    color_operation: BlendOperation::Add,
+                    destination_color: BlendFactor::OneMinusSourceAlpha,
+                    source_alpha: BlendFactor::One,
+                    alpha_operation: BlendOperation::Add,
+                    destination_alpha: BlendFactor::Zero,
+                    alpha_sample_to_coverage: true,
+                },
+            )],
+            floats: vec![ParamData::new(ParamId::CustomFloat8, 0.4)],
+            booleans: vec![
+                ParamData::new(ParamId::CustomBoolean1, true),
+                ParamData::new(ParamId::CustomBoolean3, false),
+            ],
+            vectors: vec![ParamData::new(
+                ParamId::CustomVector13,
+                Vector4::new(0.1, 0.2, 0.3, 1.0),
+            )],
+            rasterizer_states: vec![ParamData::new(
+                ParamId::RasterizerState0,
+                RasterizerStateData {
+                    fill_mode: FillMode::Solid,
+                    cull_mode: CullMode::Disabled,
+                    depth_bias: 0.5,
+                },
+            )],
+            samplers: vec![ParamData::new(
+                ParamId::Sampler0,
+                SamplerData {
+                    wraps: WrapMode::ClampToEdge,
+                    wrapt: WrapMode::MirroredRepeat,
+                    wrapr: WrapMode::Repeat,
+                    min_filter: MinFilter::LinearMipmapLinear,
+                    mag_filter: MagFilter::Linear,
+                    border_color: Color4f {
+                        r: 0.0,
+                        g: 0.5,
+                        b: 1.0,
+                        a: 1.0,
+                    },
+                    lod_bias: -0.5,
+                    max_anisotropy: MaxAnisotropy::Four,
+                },
+            )],
+            textures: vec![ParamData::new(ParamId::Texture0, "mario_col".to_owned())],
+            uv_transforms: vec![ParamData::new(
+                ParamId::UvTransform0,
+                UvTransform {
+                    scale_u: 2.0,
+                    scale_v: 0.5,
+                    rotation: 0.25,
+                    translate_u: 0.1,
+                    translate_v: -0.1,
+                },
+            )],
+        }];
+
+        // Use non-default values to check that every exported field is imported.
+        let xml = export_xml_presets(&entries).unwrap();
+        assert_eq!(
+            XmlPresets {
+                entries,
+                unparsed_fields: Vec::new()
+            },
+            parse_xml_presets(xml.as_bytes()).unwrap()
+        );
+    }
+
+    #[test]
+    fn xml_presets_cross_mod_fields() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<MaterialLibrary>
+  <material materialLabel="a" shaderLabel="SFX_PBS_0100000008008269_opaque">
+    <param name="BlendState0">
+      <BlendState>
+        <SrcColor>One</SrcColor>
+        <Unk2>0</Unk2>
+        <DstColor>OneMinusSourceAlpha</DstColor>
+        <Unk4>1</Unk4>
+        <Unk5>0</Unk5>
+        <Unk6>0</Unk6>
+        <Unk7>1</Unk7>
+      </BlendState>
+    </param>
+    <param name="Sampler0">
+      <Sampler>
+        <WrapS>Repeat</WrapS>
+        <WrapT>invalid</WrapT>
+      </Sampler>
+    </param>
+    <param name="Texture0">
+      <MatlString>mario_col</MatlString>
+    </param>
+    <param name="NotAParam">0</param>
+  </material>
+</MaterialLibrary>"#;
+
+        let presets = parse_xml_presets(xml.as_bytes()).unwrap();
+        assert_eq!(
+            BlendStateData {
+                source_color: BlendFactor::One,
+                color_operation: BlendOperation::Add,
+                destination_color: BlendFactor::OneMinusSourceAlpha,
+                source_alpha: BlendFactor::One,
+                alpha_operation: BlendOperation::Add,
+                destination_alpha: BlendFactor::Zero,
+                alpha_sample_to_coverage: true,
+            },
+            presets.entries[0].blend_states[0].data
+        );
+        assert_eq!(WrapMode::Repeat, presets.entries[0].samplers[0].data.wraps);
+        assert_eq!("mario_col", presets.entries[0].textures[0].data);
+        assert_eq!(
+            vec![
+                "a/Sampler0/WrapT",
+                "a/Sampler0/WrapR",
+                "a/Sampler0/MinFilter",
+                "a/Sampler0/MagFilter",
+                "a/Sampler0/BorderColor/X",
+                "a/Sampler0/BorderColor/Y",
+                "a/Sampler0/BorderColor/Z",
+                "a/Sampler0/BorderColor/W",
+                "a/Sampler0/LodBias",
+                "a/Sampler0/MaxAnisotropy",
+                "a/NotAParam",
+            ],
+            presets.unparsed_fields
+        );
+    }
+
+    #[test]
+    fn xml_presets_cross_mod_integer_enums() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<MaterialLibrary>
+  <material materialLabel="a" shaderLabel="SFX_PBS_0100000008008269_opaque">
+    <param name="BlendState0">
+      <BlendState>
+        <SourceColor>2</SourceColor>
+        <ColorOperation>0</ColorOperation>
+        <DestinationColor>6</DestinationColor>
+        <SourceAlpha>1</SourceAlpha>
+        <AlphaOperation>0</AlphaOperation>
+        <DestinationAlpha>0</DestinationAlpha>
+        <AlphaSampleToCoverage>0</AlphaSampleToCoverage>
+      </BlendState>
+    </param>
+    <param name="RasterizerState0">
+      <RasterizerState>
+        <FillMode>1</FillMode>
+        <CullMode>2</CullMode>
+        <DepthBias>0</DepthBias>
+      </RasterizerState>
+    </param>
+    <param name="Sampler0">
+      <Sampler>
+        <WrapS>1</WrapS>
+        <WrapT>2</WrapT>
+        <WrapR>0</WrapR>
+        <MinFilter>1</MinFilter>
+        <MagFilter>2</MagFilter>
+      </Sampler>
+    </param>
+  </material>
+</MaterialLibrary>"#;
+
+        let presets = parse_xml_presets(xml.as_bytes()).unwrap();
+        let entry = &presets.entries[0];
+        assert_eq!(
+            BlendStateData {
+                source_color: BlendFactor::SourceAlpha,
+                color_operation: BlendOperation::Add,
+                destination_color: BlendFactor::OneMinusSourceAlpha,
+                source_alpha: BlendFactor::One,
+                alpha_operation: BlendOperation::Add,
+                destination_alpha: BlendFactor::Zero,
+                alpha_sample_to_coverage: false,
+            },
+            entry.blend_states[0].data
+        );
+        assert_eq!(
+            RasterizerStateData {
+                fill_mode: FillMode::Solid,
+                cull_mode: CullMode::Disabled,
+                depth_bias: 0.0,
+            },
+            entry.rasterizer_states[0].data
+        );
+
+        let sampler = &entry.samplers[0].data;
+        assert_eq!(WrapMode::ClampToEdge, sampler.wraps);
+        assert_eq!(WrapMode::MirroredRepeat, sampler.wrapt);
+        assert_eq!(WrapMode::Repeat, sampler.wrapr);
+        assert_eq!(MinFilter::LinearMipmapLinear, sampler.min_filter);
+        assert_eq!(MagFilter::Linear2, sampler.mag_filter);
+
+        // Out of range values are not valid variants.
+        assert_eq!(None, parse_enum::<WrapMode>("4"));
+        assert_eq!(None, parse_enum::<CullMode>("-1"));
+    }
+
+    #[test]
+    fn export_xml_presets_cross_mod_integer_enums() {
+        let entries = vec![MatlEntryData {
+            blend_states: vec![ParamData::new(
+                ParamId::BlendState0,
+                BlendStateData {
+                    source_color: BlendFactor::SourceAlpha,
+                    color_operation: BlendOperation::Add,
+                    destination_color: BlendFactor::OneMinusSourceAlpha,
+                    source_alpha: BlendFactor::One,
+                    alpha_operation: BlendOperation::Add,
+                    destination_alpha: BlendFactor::Zero,
+                    alpha_sample_to_coverage: false,
+                },
+            )],
+            rasterizer_states: vec![ParamData::new(
+                ParamId::RasterizerState0,
+                RasterizerStateData {
+                    fill_mode: FillMode::Solid,
+                    cull_mode: CullMode::Disabled,
+                    depth_bias: 0.0,
+                },
+            )],
+            samplers: vec![ParamData::new(
+                ParamId::Sampler0,
+                SamplerData {
+                    wraps: WrapMode::ClampToEdge,
+                    wrapt: WrapMode::MirroredRepeat,
+                    wrapr: WrapMode::Repeat,
+                    min_filter: MinFilter::LinearMipmapLinear,
+                    mag_filter: MagFilter::Linear2,
+                    border_color: Color4f {
+                        r: 0.0,
+                        g: 0.0,
+                        b: 0.0,
+                        a: 1.0,
+                    },
+                    lod_bias: 0.0,
+                    max_anisotropy: MaxAnisotropy::One,
+                },
+            )],
+            ..default_material()
+        }];
+
+        let xml = export_xml_presets(&entries).unwrap();
+        for text in [
+            "<SourceColor>2</SourceColor>",
+            "<ColorOperation>0</ColorOperation>",
+            "<DestinationColor>6</DestinationColor>",
+            "<SourceAlpha>1</SourceAlpha>",
+            "<AlphaOperation>0</AlphaOperation>",
+            "<DestinationAlpha>0</DestinationAlpha>",
+            "<FillMode>1</FillMode>",
+            "<CullMode>2</CullMode>",
+            "<WrapS>1</WrapS>",
+            "<WrapT>2</WrapT>",
+            "<WrapR>0</WrapR>",
+            "<MinFilter>1</MinFilter>",
+            "<MagFilter>2</MagFilter>",
+        ] {
+            assert!(xml.contains(text), "{text}");
+        }
+        assert_eq!(entries, load_xml_presets(xml.as_bytes()).unwrap());
+    }
 }
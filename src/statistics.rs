@@ -0,0 +1,193 @@
+use nutexb::{NutexbFile, NutexbFormat};
+use ssbh_data::mesh_data::{AttributeData, MeshObjectData};
+use ssbh_wgpu::ModelFolder;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MeshObjectStatistics {
+    pub name: String,
+    pub subindex: u64,
+    pub vertex_count: usize,
+    pub index_count: usize,
+    pub attribute_size: usize,
+    pub bone_count: usize,
+}
+
+impl MeshObjectStatistics {
+    pub fn from_mesh_object(o: &MeshObjectData) -> Self {
+        Self {
+            name: o.name.clone(),
+            subindex: o.subindex,
+            vertex_count: o.vertex_count().unwrap_or_default(),
+            index_count: o.vertex_indices.len(),
+            attribute_size: attribute_size(o),
+            bone_count: o.bone_influences.len(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TextureStatistics {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
+    pub format: NutexbFormat,
+    pub mipmap_count: u32,
+    pub layer_count: u32,
+    pub size: usize,
+}
+
+impl TextureStatistics {
+    pub fn from_nutexb(name: &str, nutexb: &NutexbFile) -> Self {
+        Self {
+            name: name.to_owned(),
+            width: nutexb.footer.width,
+            height: nutexb.footer.height,
+            depth: nutexb.footer.depth,
+            format: nutexb.footer.image_format,
+            mipmap_count: nutexb.footer.mipmap_count,
+            layer_count: nutexb.footer.layer_count,
+            size: nutexb.footer.data_size as usize,
+        }
+    }
+}
+
+/// Statistics for the model.numshb, model.nusktb, and textures in a model folder.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ModelFolderStatistics {
+    pub mesh_objects: Vec<MeshObjectStatistics>,
+    pub skel_bone_count: usize,
+    pub textures: Vec<TextureStatistics>,
+}
+
+impl ModelFolderStatistics {
+    pub fn from_model(model: &ModelFolder) -> Self {
+        Self {
+            mesh_objects: model
+                .find_mesh()
+                .map(|mesh| {
+                    mesh.objects
+                        .iter()
+                        .map(MeshObjectStatistics::from_mesh_object)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            skel_bone_count: model
+                .find_skel()
+                .map(|skel| skel.bones.len())
+                .unwrap_or_default(),
+            textures: model
+                .nutexbs
+                .iter()
+                .filter_map(|(name, n)| Some(TextureStatistics::from_nutexb(name, n.as_ref()?)))
+                .collect(),
+        }
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.mesh_objects.iter().map(|o| o.vertex_count).sum()
+    }
+
+    pub fn index_count(&self) -> usize {
+        self.mesh_objects.iter().map(|o| o.index_count).sum()
+    }
+
+    pub fn attribute_size(&self) -> usize {
+        self.mesh_objects.iter().map(|o| o.attribute_size).sum()
+    }
+
+    pub fn texture_size(&self) -> usize {
+        self.textures.iter().map(|t| t.size).sum()
+    }
+}
+
+/// The approximate size in bytes of the vertex buffers for `o`.
+pub fn attribute_size(o: &MeshObjectData) -> usize {
+    // Estimate the size using the data types of in game models.
+    // Positions use floats, vectors use half floats, and colors use bytes.
+    let total_size = |attributes: &[AttributeData], vertex_size: usize| {
+        attributes
+            .iter()
+            .map(|a| a.data.len() * vertex_size)
+            .sum::<usize>()
+    };
+
+    total_size(&o.positions, 12)
+        + total_size(&o.normals, 8)
+        + total_size(&o.tangents, 8)
+        + total_size(&o.binormals, 12)
+        + total_size(&o.texture_coordinates, 8)
+        + total_size(&o.color_sets, 4)
+}
+
+pub fn format_size(size: usize) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = 1024.0 * 1024.0;
+
+    let size = size as f64;
+    if size >= MB {
+        format!("{:.2} MB", size / MB)
+    } else if size >= KB {
+        format!("{:.2} KB", size / KB)
+    } else {
+        format!("{size} B")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ssbh_data::mesh_data::{BoneInfluence, VectorData};
+
+    use super::*;
+
+    #[test]
+    fn mesh_object_statistics() {
+        let o = MeshObjectData {
+            name: "a".to_owned(),
+            subindex: 1,
+            vertex_indices: vec![0, 1, 2],
+            positions: vec![AttributeData {
+                name: "Position0".to_owned(),
+                data: VectorData::Vector3(vec![[0.0; 3]; 3]),
+            }],
+            texture_coordinates: vec![AttributeData {
+                name: "map1".to_owned(),
+                data: VectorData::Vector2(vec![[0.0; 2]; 3]),
+            }],
+            color_sets: vec![AttributeData {
+                name: "colorSet1".to_owned(),
+                data: VectorData::Vector4(vec![[0.0; 4]; 3]),
+            }],
+            bone_influences: vec![
+                BoneInfluence {
+                    bone_name: "a".to_owned(),
+                    vertex_weights: Vec::new(),
+                },
+                BoneInfluence {
+                    bone_name: "b".to_owned(),
+                    vertex_weights: Vec::new(),
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            MeshObjectStatistics {
+                name: "a".to_owned(),
+                subindex: 1,
+                vertex_count: 3,
+                index_count: 3,
+                attribute_size: 3 * 12 + 3 * 8 + 3 * 4,
+                bone_count: 2,
+            },
+            MeshObjectStatistics::from_mesh_object(&o)
+        );
+    }
+
+    #[test]
+    fn format_sizes() {
+        assert_eq!("512 B", format_size(512));
+        assert_eq!("2.00 KB", format_size(2048));
+        assert_eq!("1.50 MB", format_size(1024 * 1024 * 3 / 2));
+    }
+}
@@ -0,0 +1,187 @@
+use anyhow::anyhow;
+use prc::{ParamKind, ParamStruct, hash40::hash40};
+use ssbh_wgpu::swing::{Capsule, Oval, Param, Plane, Sphere, SwingPrc};
+use std::path::Path;
+
+/// The name and accessors for an editable float field.
+pub struct FloatField<T> {
+    pub name: &'static str,
+    pub get: fn(&T) -> f32,
+    pub get_mut: fn(&mut T) -> &mut f32,
+}
+
+macro_rules! float_field {
+    ($field:ident) => {
+        FloatField {
+            name: stringify!($field),
+            get: |v| v.$field,
+            get_mut: |v| &mut v.$field,
+        }
+    };
+}
+
+// Names match the prc field names to support writing values back to the prc file.
+pub const PARAM_FIELDS: [FloatField<Param>; 13] = [
+    float_field!(airresistance),
+    float_field!(waterresistance),
+    float_field!(minangle_z),
+    float_field!(maxangle_z),
+    float_field!(minangle_y),
+    float_field!(maxangle_y),
+    float_field!(collisionsizetip),
+    float_field!(collisionsizeroot),
+    float_field!(frictionrate),
+    float_field!(goalstrength),
+    float_field!(localgravity),
+    float_field!(fallspeedscale),
+    float_field!(windaffect),
+];
+
+pub const SPHERE_FIELDS: [FloatField<Sphere>; 4] = [
+    float_field!(cx),
+    float_field!(cy),
+    float_field!(cz),
+    float_field!(radius),
+];
+
+pub const OVAL_FIELDS: [FloatField<Oval>; 7] = [
+    float_field!(radius),
+    float_field!(start_offset_x),
+    float_field!(start_offset_y),
+    float_field!(start_offset_z),
+    float_field!(end_offset_x),
+    float_field!(end_offset_y),
+    float_field!(end_offset_z),
+];
+
+pub const CAPSULE_FIELDS: [FloatField<Capsule>; 8] = [
+    float_field!(start_offset_x),
+    float_field!(start_offset_y),
+    float_field!(start_offset_z),
+    float_field!(end_offset_x),
+    float_field!(end_offset_y),
+    float_field!(end_offset_z),
+    float_field!(start_radius),
+    float_field!(end_radius),
+];
+
+pub const PLANE_FIELDS: [FloatField<Plane>; 4] = [
+    float_field!(nx),
+    float_field!(ny),
+    float_field!(nz),
+    float_field!(distance),
+];
+
+/// Write the edited values in `swing_prc` to `path`.
+/// Other values are copied from the prc file at `source_path` since they aren't editable.
+pub fn save_swing_prc(swing_prc: &SwingPrc, source_path: &Path, path: &Path) -> anyhow::Result<()> {
+    let mut prc = prc::open(source_path)?;
+
+    if let Some(ParamKind::List(swing_bones)) = find_param_mut(&mut prc, "swingbones") {
+        for (swing_bone, bone) in swing_bones.0.iter_mut().zip(&swing_prc.swingbones) {
+            if let ParamKind::Struct(swing_bone) = swing_bone {
+                update_list(swing_bone, "params", &bone.params, &PARAM_FIELDS)?;
+            }
+        }
+    }
+    update_list(&mut prc, "spheres", &swing_prc.spheres, &SPHERE_FIELDS)?;
+    update_list(&mut prc, "ovals", &swing_prc.ovals, &OVAL_FIELDS)?;
+    update_list(&mut prc, "capsules", &swing_prc.capsules, &CAPSULE_FIELDS)?;
+    update_list(&mut prc, "planes", &swing_prc.planes, &PLANE_FIELDS)?;
+
+    prc::save(path, &prc)?;
+    Ok(())
+}
+
+fn update_list<T>(
+    prc: &mut ParamStruct,
+    name: &str,
+    values: &[T],
+    fields: &[FloatField<T>],
+) -> anyhow::Result<()> {
+    if let Some(ParamKind::List(list)) = find_param_mut(prc, name) {
+        for (i, (item, value)) in list.0.iter_mut().zip(values).enumerate() {
+            if let ParamKind::Struct(item) = item {
+                for field in fields {
+                    // Skipping fields would silently discard edits when saving.
+                    match find_param_mut(item, field.name) {
+                        Some(ParamKind::Float(f)) => *f = (field.get)(value),
+                        _ => {
+                            return Err(anyhow!(
+                                "Missing float field {} for {name}[{i}].",
+                                field.name
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn find_param_mut<'a>(prc: &'a mut ParamStruct, name: &str) -> Option<&'a mut ParamKind> {
+    let hash = hash40(name);
+    prc.0.iter_mut().find(|(h, _)| *h == hash).map(|(_, p)| p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prc::ParamList;
+
+    struct Value {
+        x: f32,
+    }
+
+    const VALUE_FIELDS: [FloatField<Value>; 1] = [float_field!(x)];
+
+    fn param_struct(values: Vec<(&str, ParamKind)>) -> ParamStruct {
+        ParamStruct(values.into_iter().map(|(n, p)| (hash40(n), p)).collect())
+    }
+
+    #[test]
+    fn update_list_float_fields() {
+        let item = |x| {
+            ParamKind::Struct(param_struct(vec![
+                ("name", ParamKind::Hash(hash40("a"))),
+                ("x", ParamKind::Float(x)),
+            ]))
+        };
+        let mut prc = param_struct(vec![(
+            "values",
+            ParamKind::List(ParamList(vec![item(1.0), item(2.0), item(3.0)])),
+        )]);
+
+        // Extra items in the file should be unchanged.
+        let values = vec![Value { x: 4.0 }, Value { x: 5.0 }];
+        update_list(&mut prc, "values", &values, &VALUE_FIELDS).unwrap();
+
+        assert_eq!(
+            param_struct(vec![(
+                "values",
+                ParamKind::List(ParamList(vec![item(4.0), item(5.0), item(3.0)]))
+            )]),
+            prc
+        );
+    }
+
+    #[test]
+    fn update_list_missing_field() {
+        let mut prc = param_struct(vec![(
+            "values",
+            ParamKind::List(ParamList(vec![ParamKind::Struct(param_struct(vec![(
+                "y",
+                ParamKind::Float(1.0),
+            )]))])),
+        )]);
+
+        let values = vec![Value { x: 4.0 }];
+        assert_eq!(
+            "Missing float field x for values[0].",
+            update_list(&mut prc, "values", &values, &VALUE_FIELDS)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+}
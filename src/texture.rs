@@ -0,0 +1,707 @@
+use anyhow::anyhow;
+use image::RgbaImage;
+use image_dds::{ImageFormat, Mipmaps, Quality, Surface, SurfaceRgba8, ddsfile::Dds};
+use log::error;
+use nutexb::{NutexbFile, NutexbFooter, NutexbFormat};
+use ssbh_data::{matl_data::ParamId, prelude::*};
+use std::{
+    io::{BufReader, BufWriter},
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+};
+use tegra_swizzle::{BlockDim, surface::swizzled_surface_size};
+use thiserror::Error;
+
+use crate::validation::expects_srgb;
+
+/// The image file formats supported for exporting nutexb files.
+pub const EXPORT_EXTENSIONS: [&str; 3] = ["png", "tga", "dds"];
+
+/// The image file formats supported for importing nutexb files.
+pub const IMPORT_EXTENSIONS: [&str; 3] = ["png", "tga", "dds"];
+
+/// The nutexb formats supported for importing images and converting nutexb files.
+pub const IMPORT_FORMATS: [NutexbFormat; 8] = [
+    NutexbFormat::BC1Unorm,
+    NutexbFormat::BC1Srgb,
+    NutexbFormat::BC3Unorm,
+    NutexbFormat::BC3Srgb,
+    NutexbFormat::BC7Unorm,
+    NutexbFormat::BC7Srgb,
+    NutexbFormat::R8G8B8A8Unorm,
+    NutexbFormat::R8G8B8A8Srgb,
+];
+
+pub fn image_format(format: NutexbFormat) -> ImageFormat {
+    match format {
+        NutexbFormat::R8Unorm => ImageFormat::R8Unorm,
+        NutexbFormat::R8G8B8A8Unorm => ImageFormat::Rgba8Unorm,
+        NutexbFormat::R8G8B8A8Srgb => ImageFormat::Rgba8UnormSrgb,
+        NutexbFormat::R32G32B32A32Float => ImageFormat::Rgba32Float,
+        NutexbFormat::B8G8R8A8Unorm => ImageFormat::Bgra8Unorm,
+        NutexbFormat::B8G8R8A8Srgb => ImageFormat::Bgra8UnormSrgb,
+        NutexbFormat::BC1Unorm => ImageFormat::BC1RgbaUnorm,
+        NutexbFormat::BC1Srgb => ImageFormat::BC1RgbaUnormSrgb,
+        NutexbFormat::BC2Unorm => ImageFormat::BC2RgbaUnorm,
+        NutexbFormat::BC2Srgb => ImageFormat::BC2RgbaUnormSrgb,
+        NutexbFormat::BC3Unorm => ImageFormat::BC3RgbaUnorm,
+        NutexbFormat::BC3Srgb => ImageFormat::BC3RgbaUnormSrgb,
+        NutexbFormat::BC4Unorm => ImageFormat::BC4RUnorm,
+        NutexbFormat::BC4Snorm => ImageFormat::BC4RSnorm,
+        NutexbFormat::BC5Unorm => ImageFormat::BC5RgUnorm,
+        NutexbFormat::BC5Snorm => ImageFormat::BC5RgSnorm,
+        NutexbFormat::BC6Ufloat => ImageFormat::BC6hRgbUfloat,
+        NutexbFormat::BC6Sfloat => ImageFormat::BC6hRgbSfloat,
+        NutexbFormat::BC7Unorm => ImageFormat::BC7RgbaUnorm,
+        NutexbFormat::BC7Srgb => ImageFormat::BC7RgbaUnormSrgb,
+    }
+}
+
+/// Deswizzle the image data for all layers and mipmaps of `nutexb`.
+pub fn nutexb_surface(nutexb: &NutexbFile) -> anyhow::Result<Surface<Vec<u8>>> {
+    Ok(Surface {
+        width: nutexb.footer.width,
+        height: nutexb.footer.height,
+        depth: nutexb.footer.depth,
+        layers: nutexb.footer.layer_count,
+        mipmaps: nutexb.footer.mipmap_count,
+        image_format: image_format(nutexb.footer.image_format),
+        data: nutexb.deswizzled_data()?,
+    })
+}
+
+/// Calculate the size in bytes of the swizzled image data described by `footer`.
+pub fn expected_data_size(footer: &NutexbFooter) -> usize {
+    let format = footer.image_format;
+    let block_dim = BlockDim {
+        width: NonZeroU32::new(format.block_width()).unwrap(),
+        height: NonZeroU32::new(format.block_height()).unwrap(),
+        depth: NonZeroU32::new(format.block_depth()).unwrap(),
+    };
+    swizzled_surface_size(
+        footer.width,
+        footer.height,
+        footer.depth,
+        block_dim,
+        None,
+        format.bytes_per_pixel(),
+        footer.mipmap_count,
+        footer.layer_count,
+    )
+}
+
+/// The number of mipmaps down to a 1x1x1 base level for the given dimensions.
+pub fn max_mipmap_count(width: u32, height: u32, depth: u32) -> u32 {
+    width.max(height).max(depth).max(1).ilog2() + 1
+}
+
+/// Export `nutexb` to a DDS, PNG, or TGA file based on the extension of `path`.
+pub fn export_nutexb<P: AsRef<Path>>(nutexb: &NutexbFile, path: P) -> anyhow::Result<()> {
+    let path = path.as_ref();
+    match extension(path).as_str() {
+        "dds" => export_dds(nutexb, path),
+        _ => export_images(nutexb, path),
+    }
+}
+
+/// Export `nutexb` to DDS without decoding to preserve the compressed format.
+fn export_dds(nutexb: &NutexbFile, path: &Path) -> anyhow::Result<()> {
+    let dds = nutexb_surface(nutexb)?.to_dds()?;
+    let mut writer = BufWriter::new(std::fs::File::create(path)?);
+    dds.write(&mut writer)?;
+    Ok(())
+}
+
+/// Export each layer, depth slice, and mipmap of `nutexb` to a separate image file.
+fn export_images(nutexb: &NutexbFile, path: &Path) -> anyhow::Result<()> {
+    let surface = nutexb_surface(nutexb)?.decode_rgba8()?;
+    for layer in 0..surface.layers {
+        for mipmap in 0..surface.mipmaps {
+            // Depth is also halved for each mipmap.
+            for depth in 0..mip_dimension(surface.depth, mipmap) {
+                let data = surface
+                    .get(layer, depth, mipmap)
+                    .ok_or_else(|| anyhow!("Missing data for layer {layer} mipmap {mipmap}."))?;
+
+                let width = mip_dimension(surface.width, mipmap);
+                let height = mip_dimension(surface.height, mipmap);
+                let image = RgbaImage::from_raw(width, height, data.to_vec())
+                    .ok_or_else(|| anyhow!("Invalid image data for layer {layer}."))?;
+
+                let counts = [surface.layers, surface.depth, surface.mipmaps];
+                image.save(image_path(path, counts, [layer, depth, mipmap]))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Convert the PNG, TGA, or DDS image at `path` to a nutexb with generated mipmaps.
+pub fn import_nutexb<P: AsRef<Path>>(
+    path: P,
+    format: NutexbFormat,
+    name: &str,
+) -> anyhow::Result<NutexbFile> {
+    let path = path.as_ref();
+    let image_format = image_format(format);
+
+    // Mipmaps are regenerated from the base level to match the new format.
+    let surface = match extension(path).as_str() {
+        "dds" => {
+            let dds = Dds::read(BufReader::new(std::fs::File::open(path)?))?;
+            Surface::from_dds(&dds)?.decode_rgba8()?.encode(
+                image_format,
+                Quality::Normal,
+                Mipmaps::GeneratedAutomatic,
+            )?
+        }
+        _ => {
+            let image = image::open(path)?.into_rgba8();
+            SurfaceRgba8::from_image(&image).encode(
+                image_format,
+                Quality::Normal,
+                Mipmaps::GeneratedAutomatic,
+            )?
+        }
+    };
+
+    nutexb_from_surface(surface, format, name)
+}
+
+/// Convert `nutexb` to `format` while preserving the existing mipmaps.
+/// Switching between the sRGB and linear variants of a format only updates the footer.
+pub fn convert_nutexb(nutexb: &mut NutexbFile, format: NutexbFormat) -> anyhow::Result<()> {
+    if linear_format(nutexb.footer.image_format) == linear_format(format) {
+        // The swizzled data has the same block layout for both variants.
+        nutexb.footer.image_format = format;
+    } else {
+        let surface = nutexb_surface(nutexb)?.decode_rgba8()?.encode(
+            image_format(format),
+            Quality::Normal,
+            Mipmaps::FromSurface,
+        )?;
+        let name = nutexb.footer.string.to_string();
+        *nutexb = nutexb_from_surface(surface, format, &name)?;
+    }
+    Ok(())
+}
+
+/// The variant of `format` that matches `srgb` with the same compression if possible.
+pub fn srgb_format(format: NutexbFormat, srgb: bool) -> NutexbFormat {
+    match (linear_format(format), srgb) {
+        (format, false) => format,
+        (NutexbFormat::R8G8B8A8Unorm, true) => NutexbFormat::R8G8B8A8Srgb,
+        (NutexbFormat::B8G8R8A8Unorm, true) => NutexbFormat::B8G8R8A8Srgb,
+        (NutexbFormat::BC1Unorm, true) => NutexbFormat::BC1Srgb,
+        (NutexbFormat::BC2Unorm, true) => NutexbFormat::BC2Srgb,
+        (NutexbFormat::BC3Unorm, true) => NutexbFormat::BC3Srgb,
+        // Formats without an sRGB variant need to be compressed again.
+        (_, true) => NutexbFormat::BC7Srgb,
+    }
+}
+
+fn linear_format(format: NutexbFormat) -> NutexbFormat {
+    match format {
+        NutexbFormat::R8G8B8A8Srgb => NutexbFormat::R8G8B8A8Unorm,
+        NutexbFormat::B8G8R8A8Srgb => NutexbFormat::B8G8R8A8Unorm,
+        NutexbFormat::BC1Srgb => NutexbFormat::BC1Unorm,
+        NutexbFormat::BC2Srgb => NutexbFormat::BC2Unorm,
+        NutexbFormat::BC3Srgb => NutexbFormat::BC3Unorm,
+        NutexbFormat::BC7Srgb => NutexbFormat::BC7Unorm,
+        _ => format,
+    }
+}
+
+fn nutexb_from_surface(
+    surface: Surface<Vec<u8>>,
+    format: NutexbFormat,
+    name: &str,
+) -> anyhow::Result<NutexbFile> {
+    let nutexb = NutexbFile::from_surface(
+        nutexb::Surface {
+            width: surface.width,
+            height: surface.height,
+            depth: surface.depth,
+            layer_count: surface.layers,
+            mipmap_count: surface.mipmaps,
+            image_format: format,
+            data: surface.data,
+        },
+        name,
+    )?;
+    Ok(nutexb)
+}
+
+/// A texture parameter in a matl file that references a nutexb file.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TextureReference {
+    pub matl: String,
+    pub material_label: String,
+    pub param_id: ParamId,
+}
+
+/// Find all of the texture parameters in `matls` that reference the nutexb `name`.
+pub fn texture_references(
+    matls: &[(String, Option<MatlData>)],
+    name: &str,
+) -> Vec<TextureReference> {
+    let mut references = Vec::new();
+    for (matl_name, matl) in matls {
+        for entry in matl.iter().flat_map(|m| &m.entries) {
+            for texture in &entry.textures {
+                if texture.data.eq_ignore_ascii_case(name) {
+                    references.push(TextureReference {
+                        matl: matl_name.clone(),
+                        material_label: entry.material_label.clone(),
+                        param_id: texture.param_id,
+                    });
+                }
+            }
+        }
+    }
+    references
+}
+
+/// Find the first texture parameter in `matls` that references the nutexb `name`.
+pub fn assigned_texture_param(matls: &[(String, Option<MatlData>)], name: &str) -> Option<ParamId> {
+    texture_references(matls, name)
+        .first()
+        .map(|reference| reference.param_id)
+}
+
+/// Find the file names in `nutexbs` that aren't referenced by any material in `matls`.
+pub fn unused_nutexbs(
+    nutexbs: &[(String, Option<NutexbFile>)],
+    matls: &[(String, Option<MatlData>)],
+) -> Vec<String> {
+    nutexbs
+        .iter()
+        .map(|(f, _)| f)
+        .filter(|f| texture_references(matls, &nutexb_name(f)).is_empty())
+        .cloned()
+        .collect()
+}
+
+/// Value ranges and a histogram for each RGBA channel of decoded image data.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ChannelStatistics {
+    pub min: [f32; 4],
+    pub max: [f32; 4],
+    pub mean: [f32; 4],
+    /// The number of pixels with each 8-bit value for each channel.
+    pub histogram: [[u32; 256]; 4],
+}
+
+#[derive(Debug, PartialEq, Error)]
+pub enum ChannelWarning {
+    #[error(
+        "The metalness channel (R) is entirely 1.0, so materials using this texture for {param} will be fully metallic."
+    )]
+    FullyMetallic { param: ParamId },
+
+    #[error(
+        "The blue channel has a mean of {mean:.3}, which is below 0.5. Normal maps for {param} should use a blue channel close to 1.0 unless the channel is used as a transition mask."
+    )]
+    NonNeutralNormalBlue { param: ParamId, mean: f32 },
+}
+
+/// Calculate channel statistics for the tightly packed RGBA8 values in `rgba`.
+pub fn channel_statistics(rgba: &[u8]) -> ChannelStatistics {
+    let mut min = [u8::MAX; 4];
+    let mut max = [0u8; 4];
+    let mut sum = [0u64; 4];
+    let mut histogram = [[0u32; 256]; 4];
+
+    let pixel_count = rgba.len() / 4;
+    for pixel in rgba.chunks_exact(4) {
+        for (c, value) in pixel.iter().copied().enumerate() {
+            min[c] = min[c].min(value);
+            max[c] = max[c].max(value);
+            sum[c] += value as u64;
+            histogram[c][value as usize] += 1;
+        }
+    }
+
+    if pixel_count == 0 {
+        min = [0; 4];
+    }
+
+    ChannelStatistics {
+        min: min.map(|v| v as f32 / 255.0),
+        max: max.map(|v| v as f32 / 255.0),
+        mean: sum.map(|v| v as f32 / pixel_count.max(1) as f32 / 255.0),
+        histogram,
+    }
+}
+
+/// Calculate channel statistics for a single `layer` and `mipmap` of `nutexb`.
+/// The layer selects the depth slice for 3D textures.
+pub fn nutexb_channel_statistics(
+    nutexb: &NutexbFile,
+    layer: u32,
+    mipmap: u32,
+) -> anyhow::Result<ChannelStatistics> {
+    let surface = nutexb_surface(nutexb)?.decode_rgba8()?;
+    let (layer, depth) = if surface.depth > 1 {
+        (0, layer)
+    } else {
+        (layer, 0)
+    };
+    let data = surface
+        .get(layer, depth, mipmap)
+        .ok_or_else(|| anyhow!("Missing data for layer {layer} mipmap {mipmap}."))?;
+    Ok(channel_statistics(data))
+}
+
+/// Check for common mistakes for textures assigned to PRM and NOR texture parameters.
+pub fn channel_warnings(
+    statistics: &ChannelStatistics,
+    param_ids: &[ParamId],
+) -> Vec<ChannelWarning> {
+    let mut warnings = Vec::new();
+    for param in param_ids {
+        match param {
+            ParamId::Texture6 if statistics.min[0] == 1.0 => {
+                warnings.push(ChannelWarning::FullyMetallic { param: *param });
+            }
+            // A dark blue channel often comes from exporting only the normal XY values.
+            ParamId::Texture4 if statistics.mean[2] < 0.5 => {
+                warnings.push(ChannelWarning::NonNeutralNormalBlue {
+                    param: *param,
+                    mean: statistics.mean[2],
+                });
+            }
+            _ => (),
+        }
+    }
+    warnings.dedup();
+    warnings
+}
+
+/// The nutexb file name without the extension as used by texture parameters.
+pub fn nutexb_name(file_name: &str) -> String {
+    Path::new(file_name)
+        .with_extension("")
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Select a format with the sRGB conversion expected by the texture `param_id`.
+pub fn default_import_format(param_id: Option<ParamId>) -> NutexbFormat {
+    match param_id {
+        Some(param_id) if !expects_srgb(param_id) => NutexbFormat::BC7Unorm,
+        _ => NutexbFormat::BC7Srgb,
+    }
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+fn mip_dimension(dimension: u32, mipmap: u32) -> u32 {
+    (dimension >> mipmap).max(1)
+}
+
+/// Add suffixes for the layer, depth, and mipmap to `path` for all but the first image.
+fn image_path(path: &Path, counts: [u32; 3], indices: [u32; 3]) -> PathBuf {
+    if indices == [0; 3] {
+        return path.to_owned();
+    }
+
+    let mut name = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    for ((label, count), index) in ["layer", "depth", "mip"].iter().zip(counts).zip(indices) {
+        if count > 1 {
+            name.push_str(&format!("_{label}{index}"));
+        }
+    }
+    if let Some(extension) = path.extension() {
+        name.push('.');
+        name.push_str(&extension.to_string_lossy());
+    }
+    path.with_file_name(name)
+}
+
+/// Export all of the nutexb files in `nutexbs` to `folder` with the given file `extension`.
+pub fn export_all_nutexbs(
+    nutexbs: &[(String, Option<NutexbFile>)],
+    folder: &Path,
+    extension: &str,
+) {
+    for (name, nutexb) in nutexbs {
+        if let Some(nutexb) = nutexb {
+            let path = folder.join(name).with_extension(extension);
+            if let Err(e) = export_nutexb(nutexb, &path) {
+                error!("Failed to export {name} to {path:?}: {e}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::default_material;
+    use ssbh_data::matl_data::{MatlEntryData, TextureParam};
+
+    #[test]
+    fn image_path_suffixes() {
+        let path = Path::new("a/def_mario_col.png");
+        assert_eq!(path, image_path(path, [1, 1, 1], [0, 0, 0]));
+        assert_eq!(path, image_path(path, [6, 1, 10], [0, 0, 0]));
+        assert_eq!(
+            Path::new("a/def_mario_col_layer2_mip3.png"),
+            image_path(path, [6, 1, 10], [2, 0, 3])
+        );
+        assert_eq!(
+            Path::new("a/def_mario_col_depth4.png"),
+            image_path(path, [1, 8, 1], [0, 4, 0])
+        );
+    }
+
+    #[test]
+    fn default_import_formats() {
+        assert_eq!(NutexbFormat::BC7Srgb, default_import_format(None));
+        assert_eq!(
+            NutexbFormat::BC7Srgb,
+            default_import_format(Some(ParamId::Texture0))
+        );
+        assert_eq!(
+            NutexbFormat::BC7Unorm,
+            default_import_format(Some(ParamId::Texture4))
+        );
+        assert_eq!(
+            NutexbFormat::BC7Unorm,
+            default_import_format(Some(ParamId::Texture6))
+        );
+    }
+
+    #[test]
+    fn assigned_texture_params() {
+        let matl = MatlData {
+            major_version: 1,
+            minor_version: 6,
+            entries: vec![MatlEntryData {
+                material_label: "a".to_owned(),
+                textures: vec![
+                    TextureParam::new(ParamId::Texture0, "def_mario_001_col".to_owned()),
+                    TextureParam::new(ParamId::Texture4, "def_mario_001_nor".to_owned()),
+                ],
+                ..default_material()
+            }],
+        };
+        let matls = vec![("model.numatb".to_owned(), Some(matl))];
+
+        assert_eq!(
+            Some(ParamId::Texture4),
+            assigned_texture_param(&matls, "DEF_MARIO_001_NOR")
+        );
+        assert_eq!(
+            Some(ParamId::Texture0),
+            assigned_texture_param(&matls, "def_mario_001_col")
+        );
+        assert_eq!(None, assigned_texture_param(&matls, "def_mario_001_prm"));
+    }
+
+    fn bc7_nutexb() -> NutexbFile {
+        NutexbFile::from_surface(
+            nutexb::Surface {
+                width: 8,
+                height: 8,
+                depth: 1,
+                layer_count: 1,
+                mipmap_count: 2,
+                image_format: NutexbFormat::BC7Srgb,
+                data: vec![0u8; 80],
+            },
+            "def_mario_001_col",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn srgb_formats() {
+        assert_eq!(
+            NutexbFormat::BC7Srgb,
+            srgb_format(NutexbFormat::BC7Unorm, true)
+        );
+        assert_eq!(
+            NutexbFormat::BC7Unorm,
+            srgb_format(NutexbFormat::BC7Srgb, false)
+        );
+        assert_eq!(
+            NutexbFormat::BC1Srgb,
+            srgb_format(NutexbFormat::BC1Srgb, true)
+        );
+        assert_eq!(
+            NutexbFormat::R8G8B8A8Unorm,
+            srgb_format(NutexbFormat::R8G8B8A8Srgb, false)
+        );
+        assert_eq!(
+            NutexbFormat::BC5Unorm,
+            srgb_format(NutexbFormat::BC5Unorm, false)
+        );
+        assert_eq!(
+            NutexbFormat::BC7Srgb,
+            srgb_format(NutexbFormat::BC5Unorm, true)
+        );
+    }
+
+    #[test]
+    fn convert_nutexb_srgb_preserves_data() {
+        let mut nutexb = bc7_nutexb();
+        let data = nutexb.data.clone();
+
+        convert_nutexb(&mut nutexb, NutexbFormat::BC7Unorm).unwrap();
+        assert_eq!(NutexbFormat::BC7Unorm, nutexb.footer.image_format);
+        assert_eq!(data, nutexb.data);
+    }
+
+    #[test]
+    fn convert_nutexb_compression() {
+        let mut nutexb = bc7_nutexb();
+
+        convert_nutexb(&mut nutexb, NutexbFormat::R8G8B8A8Srgb).unwrap();
+        assert_eq!(NutexbFormat::R8G8B8A8Srgb, nutexb.footer.image_format);
+        assert_eq!(
+            (8, 8, 2),
+            (
+                nutexb.footer.width,
+                nutexb.footer.height,
+                nutexb.footer.mipmap_count
+            )
+        );
+        assert_eq!("def_mario_001_col", nutexb.footer.string.to_string());
+        assert_eq!(expected_data_size(&nutexb.footer), nutexb.data.len());
+    }
+
+    #[test]
+    fn texture_references_unused_nutexbs() {
+        let matl = |label: &str, textures| MatlData {
+            major_version: 1,
+            minor_version: 6,
+            entries: vec![MatlEntryData {
+                material_label: label.to_owned(),
+                textures,
+                ..default_material()
+            }],
+        };
+        let matls = vec![
+            (
+                "model.numatb".to_owned(),
+                Some(matl(
+                    "a",
+                    vec![
+                        TextureParam::new(ParamId::Texture0, "def_col".to_owned()),
+                        TextureParam::new(ParamId::Texture1, "def_col".to_owned()),
+                    ],
+                )),
+            ),
+            (
+                "metamon_model.numatb".to_owned(),
+                Some(matl(
+                    "b",
+                    vec![TextureParam::new(ParamId::Texture0, "DEF_COL".to_owned())],
+                )),
+            ),
+            ("invalid.numatb".to_owned(), None),
+        ];
+
+        assert_eq!(
+            vec![
+                TextureReference {
+                    matl: "model.numatb".to_owned(),
+                    material_label: "a".to_owned(),
+                    param_id: ParamId::Texture0
+                },
+                TextureReference {
+                    matl: "model.numatb".to_owned(),
+                    material_label: "a".to_owned(),
+                    param_id: ParamId::Texture1
+                },
+                TextureReference {
+                    matl: "metamon_model.numatb".to_owned(),
+                    material_label: "b".to_owned(),
+                    param_id: ParamId::Texture0
+                }
+            ],
+            texture_references(&matls, "def_col")
+        );
+
+        let nutexbs = vec![
+            ("def_col.nutexb".to_owned(), None),
+            ("def_nor.nutexb".to_owned(), None),
+        ];
+        assert_eq!(vec!["def_nor.nutexb"], unused_nutexbs(&nutexbs, &matls));
+    }
+
+    #[test]
+    fn channel_statistics_rgba() {
+        let rgba = [0, 255, 128, 255, 255, 255, 64, 255];
+        let statistics = channel_statistics(&rgba);
+
+        assert_eq!([0.0, 1.0, 64.0 / 255.0, 1.0], statistics.min);
+        assert_eq!([1.0, 1.0, 128.0 / 255.0, 1.0], statistics.max);
+        assert_eq!([0.5, 1.0, 96.0 / 255.0, 1.0], statistics.mean);
+        assert_eq!(1, statistics.histogram[0][0]);
+        assert_eq!(1, statistics.histogram[0][255]);
+        assert_eq!(2, statistics.histogram[1][255]);
+        assert_eq!(1, statistics.histogram[2][64]);
+        assert_eq!(1, statistics.histogram[2][128]);
+    }
+
+    #[test]
+    fn channel_statistics_empty() {
+        let statistics = channel_statistics(&[]);
+        assert_eq!([0.0; 4], statistics.min);
+        assert_eq!([0.0; 4], statistics.max);
+        assert_eq!([0.0; 4], statistics.mean);
+    }
+
+    #[test]
+    fn channel_warnings_prm_nor() {
+        // Fully metallic PRM with a dark blue channel.
+        let statistics = channel_statistics(&[255, 128, 0, 255, 255, 64, 0, 255]);
+
+        assert_eq!(
+            vec![ChannelWarning::FullyMetallic {
+                param: ParamId::Texture6
+            }],
+            channel_warnings(&statistics, &[ParamId::Texture6, ParamId::Texture6])
+        );
+        assert_eq!(
+            vec![ChannelWarning::NonNeutralNormalBlue {
+                param: ParamId::Texture4,
+                mean: 0.0
+            }],
+            channel_warnings(&statistics, &[ParamId::Texture4])
+        );
+        assert!(channel_warnings(&statistics, &[ParamId::Texture0]).is_empty());
+
+        let statistics = channel_statistics(&[0, 128, 255, 255]);
+        assert!(channel_warnings(&statistics, &[ParamId::Texture4, ParamId::Texture6]).is_empty());
+
+        // Only blue channel means below 0.5 should produce a warning.
+        let statistics = channel_statistics(&[128, 128, 128, 255]);
+        assert!(channel_warnings(&statistics, &[ParamId::Texture4]).is_empty());
+    }
+
+    #[test]
+    fn max_mipmap_counts() {
+        assert_eq!(1, max_mipmap_count(1, 1, 1));
+        assert_eq!(9, max_mipmap_count(256, 256, 1));
+        assert_eq!(11, max_mipmap_count(1024, 48, 1));
+        assert_eq!(6, max_mipmap_count(16, 16, 32));
+    }
+
+    #[test]
+    fn mip_dimensions() {
+        assert_eq!(256, mip_dimension(256, 0));
+        assert_eq!(32, mip_dimension(256, 3));
+        assert_eq!(1, mip_dimension(4, 5));
+    }
+}
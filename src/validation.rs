@@ -1,9 +1,16 @@
-use crate::{FileResult, thumbnail::TextureDimension};
+use crate::{
+    FileResult,
+    bounds::{BoundingSphere, group_points},
+    preferences::ModelBudgets,
+    statistics::{MeshObjectStatistics, format_size},
+    texture::{expected_data_size, max_mipmap_count},
+    thumbnail::TextureDimension,
+};
 use approx::relative_eq;
 use nutexb::{NutexbFile, NutexbFormat};
 use ssbh_data::{
     matl_data::{BlendFactor, MagFilter, MinFilter, ParamId, WrapMode},
-    mesh_data::VectorData,
+    mesh_data::{MeshObjectData, VectorData},
     prelude::*,
 };
 use ssbh_wgpu::{ModelFolder, ShaderDatabase};
@@ -13,6 +20,7 @@ use std::{
     path::Path,
 };
 use thiserror::Error;
+use xmb_lib::{XmbEntry, XmbFile};
 
 // TODO: Add a severity level to differentiate warnings vs errors.
 #[derive(Default)]
@@ -28,6 +36,7 @@ pub struct ModelFolderValidationErrors {
     pub anim_errors: BTreeMap<usize, Vec<AnimValidationError>>,
     pub hlpb_errors: BTreeMap<usize, Vec<HlpbValidationError>>,
     pub nutexb_errors: BTreeMap<usize, Vec<NutexbValidationError>>,
+    pub xmb_errors: BTreeMap<usize, Vec<XmbValidationError>>,
 }
 
 impl ModelFolderValidationErrors {
@@ -35,6 +44,7 @@ impl ModelFolderValidationErrors {
         model: &'b ModelFolder,
         shader_database: &ShaderDatabase,
         default_texture_names: impl Iterator<Item = (&'a String, TextureDimension)> + Clone,
+        budgets: &ModelBudgets,
     ) -> Self
     where
         'b: 'a,
@@ -46,6 +56,23 @@ impl ModelFolderValidationErrors {
         if let Some((mesh_index, mesh)) = mesh {
             validate_mesh_subindices(&mut validation, mesh_index, mesh);
             validate_mesh_vertex_weights(&mut validation, mesh_index, mesh);
+            validate_mesh_budgets(&mut validation, mesh_index, mesh, budgets);
+        }
+
+        validate_texture_budgets(&mut validation, &model.nutexbs, budgets);
+        validate_nutexb_footers(&mut validation, &model.nutexbs);
+
+        if let (Some((meshex_index, meshex)), Some((_, mesh))) = (find_meshex(model), mesh) {
+            validate_meshex_bounds(&mut validation, meshex_index, meshex, mesh);
+        }
+
+        for (i, xmb) in model
+            .xmbs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (_, x))| Some((i, x.as_ref()?)))
+        {
+            validate_xmb_lod_models(&mut validation, i, xmb, &model.modls);
         }
 
         let modl = find_modl(model);
@@ -125,6 +152,15 @@ fn find_adj(model: &ModelFolder) -> Option<(usize, &AdjData)> {
         .and_then(|(i, (_, m))| Some((i, m.as_ref()?)))
 }
 
+fn find_meshex(model: &ModelFolder) -> Option<(usize, &MeshExData)> {
+    model
+        .meshexes
+        .iter()
+        .enumerate()
+        .find(|(_, (f, _))| f == "model.numshexb")
+        .and_then(|(i, (_, m))| Some((i, m.as_ref()?)))
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct MeshValidationError {
     pub mesh_object_index: usize,
@@ -166,6 +202,33 @@ pub enum MeshValidationErrorKind {
         "Mesh {mesh_name:?} has vertices with more than 4 weights and may not deform as expected in game."
     )]
     MoreThan4WeightsPerVertex { mesh_name: String },
+
+    #[error(
+        "Mesh {mesh_name:?} has {vertex_count} vertices, which exceeds the budget of {budget}."
+    )]
+    VertexCountExceedsBudget {
+        mesh_name: String,
+        vertex_count: usize,
+        budget: usize,
+    },
+
+    #[error(
+        "Mesh {mesh_name:?} is weighted to {bone_count} bones, which exceeds the budget of {budget}."
+    )]
+    BoneCountExceedsBudget {
+        mesh_name: String,
+        bone_count: usize,
+        budget: usize,
+    },
+
+    #[error(
+        "The model has {vertex_count} vertices, which exceeds the budget of {budget}. Mesh {mesh_name:?} has the most vertices."
+    )]
+    FolderVertexCountExceedsBudget {
+        mesh_name: String,
+        vertex_count: usize,
+        budget: usize,
+    },
 }
 
 pub struct SkelValidationError;
@@ -332,8 +395,6 @@ impl Display for HlpbValidationError {
     }
 }
 
-// TODO: Check size of surface for unneeded padding.
-// TODO: Check if footer data size matches actual data.
 #[derive(Debug, PartialEq, Eq, Error)]
 pub enum NutexbValidationError {
     #[error("Texture {nutexb:?} has format {format:?}, but {param} {} an sRGB format.",
@@ -348,21 +409,119 @@ pub enum NutexbValidationError {
         format: NutexbFormat,
         param: ParamId,
     },
+
+    #[error("Texture {nutexb:?} has a size of {}, which exceeds the budget of {}.", format_size(*size), format_size(*budget))]
+    SizeExceedsBudget {
+        nutexb: String,
+        size: usize,
+        budget: usize,
+    },
+
+    #[error("The textures in the folder have a total size of {}, which exceeds the budget of {}. Texture {nutexb:?} is the largest texture.", format_size(*size), format_size(*budget))]
+    FolderSizeExceedsBudget {
+        nutexb: String,
+        size: usize,
+        budget: usize,
+    },
+
+    #[error(
+        "Texture {nutexb:?} has a footer data size of {data_size} bytes, but the file contains {actual_size} bytes of image data."
+    )]
+    DataSizeMismatch {
+        nutexb: String,
+        data_size: usize,
+        actual_size: usize,
+    },
+
+    #[error(
+        "Texture {nutexb:?} has {actual_size} bytes of image data, but its dimensions, format, mipmaps, and layers require {expected_size} bytes. The texture may fail to load in game."
+    )]
+    DataSizeTooSmall {
+        nutexb: String,
+        actual_size: usize,
+        expected_size: usize,
+    },
+
+    #[error(
+        "Texture {nutexb:?} has {} bytes of unneeded padding after its {expected_size} bytes of image data.",
+        actual_size - expected_size
+    )]
+    UnneededPadding {
+        nutexb: String,
+        actual_size: usize,
+        expected_size: usize,
+    },
+
+    #[error(
+        "Texture {nutexb:?} has {mipmap_count} mipmaps, but its dimensions {width}x{height}x{depth} only support {max_mipmap_count} mipmaps."
+    )]
+    MipmapCountInvalid {
+        nutexb: String,
+        width: u32,
+        height: u32,
+        depth: u32,
+        mipmap_count: u32,
+        max_mipmap_count: u32,
+    },
+
+    #[error(
+        "Texture {nutexb:?} has compressed format {format:?}, but its dimensions {width}x{height} are not powers of two."
+    )]
+    CompressedSizeNotPowerOfTwo {
+        nutexb: String,
+        format: NutexbFormat,
+        width: u32,
+        height: u32,
+    },
+
+    #[error("Texture {nutexb:?} has the footer name {name:?}, which does not match the file name.")]
+    NameMismatch { nutexb: String, name: String },
 }
 
 impl NutexbValidationError {
     pub fn name(&self) -> &str {
         match self {
             NutexbValidationError::FormatInvalidForUsage { nutexb, .. } => nutexb,
+            NutexbValidationError::SizeExceedsBudget { nutexb, .. } => nutexb,
+            NutexbValidationError::FolderSizeExceedsBudget { nutexb, .. } => nutexb,
+            NutexbValidationError::DataSizeMismatch { nutexb, .. } => nutexb,
+            NutexbValidationError::DataSizeTooSmall { nutexb, .. } => nutexb,
+            NutexbValidationError::UnneededPadding { nutexb, .. } => nutexb,
+            NutexbValidationError::MipmapCountInvalid { nutexb, .. } => nutexb,
+            NutexbValidationError::CompressedSizeNotPowerOfTwo { nutexb, .. } => nutexb,
+            NutexbValidationError::NameMismatch { nutexb, .. } => nutexb,
         }
     }
 }
 
-pub struct MeshExValidationError;
-impl Display for MeshExValidationError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "")
-    }
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum MeshExValidationError {
+    #[error(
+        "The bounding sphere for mesh {mesh_object_full_name:?} does not contain all of its vertices. Recompute the bounds to avoid the mesh disappearing in game."
+    )]
+    BoundingSphereMissingVertices {
+        group_index: usize,
+        mesh_object_full_name: String,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum XmbValidationError {
+    #[error(
+        "Entry {entry:?} references {file_name:?}, but the file is not in the folder. Add the file or fix the reference to avoid missing LOD models in game."
+    )]
+    MissingLodModel { entry: String, file_name: String },
+}
+
+/// The attribute names used to check a shader's required attributes.
+pub fn mesh_attribute_names(o: &MeshObjectData) -> Vec<String> {
+    // Only texture coordinates and color sets are required by shaders.
+    // TODO: Avoid clone here?
+    o.texture_coordinates
+        .iter()
+        .map(|a| a.name.clone())
+        .chain(o.color_sets.iter().map(|a| a.name.clone()))
+        .collect()
 }
 
 fn validate_required_attributes(
@@ -386,13 +545,7 @@ fn validate_required_attributes(
                         })
                 }) {
                     // Find attributes required by the shader not present in the mesh.
-                    // TODO: Avoid clone here?
-                    let attribute_names: Vec<_> = o
-                        .texture_coordinates
-                        .iter()
-                        .map(|a| a.name.clone())
-                        .chain(o.color_sets.iter().map(|a| a.name.clone()))
-                        .collect();
+                    let attribute_names = mesh_attribute_names(o);
 
                     // This error can be fixed by modifying the material's shader or mesh's attributes.
                     // Add errors to the matl and mesh for clarity.
@@ -881,7 +1034,7 @@ fn validate_sampler_anisotropy(
     }
 }
 
-fn expects_srgb(texture: ParamId) -> bool {
+pub fn expects_srgb(texture: ParamId) -> bool {
     // These textures will render inaccurately with sRGB.
     // TODO: What should Texture8 use?
     !matches!(
@@ -1006,6 +1159,262 @@ fn validate_mesh_vertex_weights(
     }
 }
 
+fn validate_mesh_budgets(
+    validation: &mut ModelFolderValidationErrors,
+    mesh_index: usize,
+    mesh: &MeshData,
+    budgets: &ModelBudgets,
+) {
+    let statistics: Vec<_> = mesh
+        .objects
+        .iter()
+        .map(MeshObjectStatistics::from_mesh_object)
+        .collect();
+
+    let mut errors = Vec::new();
+    for (i, o) in statistics.iter().enumerate() {
+        if let Some(budget) = budgets.mesh_vertex_count
+            && o.vertex_count > budget
+        {
+            errors.push(MeshValidationError {
+                mesh_object_index: i,
+                kind: MeshValidationErrorKind::VertexCountExceedsBudget {
+                    mesh_name: o.name.clone(),
+                    vertex_count: o.vertex_count,
+                    budget,
+                },
+            });
+        }
+
+        if let Some(budget) = budgets.mesh_bone_count
+            && o.bone_count > budget
+        {
+            errors.push(MeshValidationError {
+                mesh_object_index: i,
+                kind: MeshValidationErrorKind::BoneCountExceedsBudget {
+                    mesh_name: o.name.clone(),
+                    bone_count: o.bone_count,
+                    budget,
+                },
+            });
+        }
+    }
+
+    // Assign the folder total to the largest mesh since it has the most room for improvement.
+    let vertex_count: usize = statistics.iter().map(|o| o.vertex_count).sum();
+    if let Some(budget) = budgets.folder_vertex_count
+        && vertex_count > budget
+        && let Some((i, o)) = statistics
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, o)| o.vertex_count)
+    {
+        errors.push(MeshValidationError {
+            mesh_object_index: i,
+            kind: MeshValidationErrorKind::FolderVertexCountExceedsBudget {
+                mesh_name: o.name.clone(),
+                vertex_count,
+                budget,
+            },
+        });
+    }
+
+    if !errors.is_empty() {
+        validation
+            .mesh_errors
+            .entry(mesh_index)
+            .or_default()
+            .extend(errors);
+    }
+}
+
+fn validate_texture_budgets(
+    validation: &mut ModelFolderValidationErrors,
+    nutexbs: &[(String, FileResult<NutexbFile>)],
+    budgets: &ModelBudgets,
+) {
+    let sizes: Vec<_> = nutexbs
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (f, n))| Some((i, f, n.as_ref()?.footer.data_size as usize)))
+        .collect();
+
+    if let Some(budget) = budgets.texture_size {
+        for (i, f, size) in &sizes {
+            if *size > budget {
+                let error = NutexbValidationError::SizeExceedsBudget {
+                    nutexb: f.to_string(),
+                    size: *size,
+                    budget,
+                };
+                validation.nutexb_errors.entry(*i).or_default().push(error);
+            }
+        }
+    }
+
+    // Assign the folder total to the largest texture since it has the most room for improvement.
+    let size: usize = sizes.iter().map(|(_, _, size)| size).sum();
+    if let Some(budget) = budgets.folder_texture_size
+        && size > budget
+        && let Some((i, f, _)) = sizes.iter().max_by_key(|(_, _, size)| *size)
+    {
+        let error = NutexbValidationError::FolderSizeExceedsBudget {
+            nutexb: f.to_string(),
+            size,
+            budget,
+        };
+        validation.nutexb_errors.entry(*i).or_default().push(error);
+    }
+}
+
+fn validate_nutexb_footers(
+    validation: &mut ModelFolderValidationErrors,
+    nutexbs: &[(String, FileResult<NutexbFile>)],
+) {
+    for (i, (f, nutexb)) in nutexbs.iter().enumerate() {
+        if let Some(nutexb) = nutexb {
+            let errors = nutexb_footer_errors(f, nutexb);
+            if !errors.is_empty() {
+                validation
+                    .nutexb_errors
+                    .entry(i)
+                    .or_default()
+                    .extend(errors);
+            }
+        }
+    }
+}
+
+fn nutexb_footer_errors(file_name: &str, nutexb: &NutexbFile) -> Vec<NutexbValidationError> {
+    let mut errors = Vec::new();
+
+    let footer = &nutexb.footer;
+    let nutexb_name = file_name.to_string();
+
+    let actual_size = nutexb.data.len();
+    if footer.data_size as usize != actual_size {
+        errors.push(NutexbValidationError::DataSizeMismatch {
+            nutexb: nutexb_name.clone(),
+            data_size: footer.data_size as usize,
+            actual_size,
+        });
+    }
+
+    let max_mipmap_count = max_mipmap_count(footer.width, footer.height, footer.depth);
+    if footer.mipmap_count == 0 || footer.mipmap_count > max_mipmap_count {
+        errors.push(NutexbValidationError::MipmapCountInvalid {
+            nutexb: nutexb_name.clone(),
+            width: footer.width,
+            height: footer.height,
+            depth: footer.depth,
+            mipmap_count: footer.mipmap_count,
+            max_mipmap_count,
+        });
+    } else {
+        // The expected size is only meaningful for valid mipmap counts.
+        let expected_size = expected_data_size(footer);
+        if actual_size < expected_size {
+            errors.push(NutexbValidationError::DataSizeTooSmall {
+                nutexb: nutexb_name.clone(),
+                actual_size,
+                expected_size,
+            });
+        } else if actual_size > expected_size {
+            errors.push(NutexbValidationError::UnneededPadding {
+                nutexb: nutexb_name.clone(),
+                actual_size,
+                expected_size,
+            });
+        }
+    }
+
+    if footer.image_format.block_width() > 1
+        && !(footer.width.is_power_of_two() && footer.height.is_power_of_two())
+    {
+        errors.push(NutexbValidationError::CompressedSizeNotPowerOfTwo {
+            nutexb: nutexb_name.clone(),
+            format: footer.image_format,
+            width: footer.width,
+            height: footer.height,
+        });
+    }
+
+    let name = footer.string.to_string();
+    let file_stem = Path::new(file_name).with_extension("");
+    if file_stem.as_os_str() != name.as_str() {
+        errors.push(NutexbValidationError::NameMismatch {
+            nutexb: nutexb_name,
+            name,
+        });
+    }
+
+    errors
+}
+
+fn validate_xmb_lod_models(
+    validation: &mut ModelFolderValidationErrors,
+    xmb_index: usize,
+    xmb: &XmbFile,
+    modls: &[(String, FileResult<ModlData>)],
+) {
+    let mut references = Vec::new();
+    numdlb_references(&xmb.entries, &mut references);
+
+    for (entry, file_name) in references {
+        if !modls.iter().any(|(f, _)| f.eq_ignore_ascii_case(file_name)) {
+            let error = XmbValidationError::MissingLodModel {
+                entry: entry.to_owned(),
+                file_name: file_name.to_owned(),
+            };
+            validation
+                .xmb_errors
+                .entry(xmb_index)
+                .or_default()
+                .push(error);
+        }
+    }
+}
+
+/// Find attribute values that reference numdlb files for each entry and its children.
+fn numdlb_references<'a>(entries: &'a [XmbEntry], references: &mut Vec<(&'a str, &'a str)>) {
+    for entry in entries {
+        for value in entry.attributes.values() {
+            if is_numdlb_file_name(value) {
+                references.push((entry.name.as_str(), value.as_str()));
+            }
+        }
+        numdlb_references(&entry.entries, references);
+    }
+}
+
+pub fn is_numdlb_file_name(value: &str) -> bool {
+    Path::new(value)
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("numdlb"))
+}
+
+fn validate_meshex_bounds(
+    validation: &mut ModelFolderValidationErrors,
+    meshex_index: usize,
+    meshex: &MeshExData,
+    mesh: &MeshData,
+) {
+    for (group_index, group) in meshex.mesh_object_groups.iter().enumerate() {
+        let points = group_points(&group.mesh_object_full_name, mesh);
+        if !BoundingSphere::from_vector4(&group.bounding_sphere).contains_points(&points) {
+            let error = MeshExValidationError::BoundingSphereMissingVertices {
+                group_index,
+                mesh_object_full_name: group.mesh_object_full_name.clone(),
+            };
+            validation
+                .meshex_errors
+                .entry(meshex_index)
+                .or_default()
+                .push(error);
+        }
+    }
+}
+
 fn validate_modl_entries(
     validation: &mut ModelFolderValidationErrors,
     modl_index: usize,
@@ -1061,12 +1470,14 @@ fn validate_modl_entries(
 mod tests {
     use nutexb::{NutexbFile, NutexbFooter, NutexbFormat};
     use ssbh_data::{
+        Vector4,
         adj_data::AdjEntryData,
         matl_data::{
             BlendFactor, BlendStateData, BlendStateParam, MatlEntryData, SamplerData, SamplerParam,
             TextureParam,
         },
         mesh_data::{AttributeData, BoneInfluence, MeshObjectData, VectorData, VertexWeight},
+        meshex_data::{EntryFlags, MeshObjectGroupData},
         modl_data::ModlEntryData,
     };
 
@@ -1152,6 +1563,33 @@ mod tests {
             }
             .to_string()
         );
+        assert_eq!(
+            r#"Mesh "mesh" has 5 vertices, which exceeds the budget of 4."#,
+            MeshValidationErrorKind::VertexCountExceedsBudget {
+                mesh_name: "mesh".to_string(),
+                vertex_count: 5,
+                budget: 4
+            }
+            .to_string()
+        );
+        assert_eq!(
+            r#"Mesh "mesh" is weighted to 5 bones, which exceeds the budget of 4."#,
+            MeshValidationErrorKind::BoneCountExceedsBudget {
+                mesh_name: "mesh".to_string(),
+                bone_count: 5,
+                budget: 4
+            }
+            .to_string()
+        );
+        assert_eq!(
+            r#"The model has 5 vertices, which exceeds the budget of 4. Mesh "mesh" has the most vertices."#,
+            MeshValidationErrorKind::FolderVertexCountExceedsBudget {
+                mesh_name: "mesh".to_string(),
+                vertex_count: 5,
+                budget: 4
+            }
+            .to_string()
+        );
     }
 
     #[test]
@@ -1295,6 +1733,18 @@ Use a Source Color of "One" or use a shader that does not premultiply alpha."#,
         );
     }
 
+    #[test]
+    fn format_meshex_errors() {
+        assert_eq!(
+            r#"The bounding sphere for mesh "mesh_VIS_O_OBJShape" does not contain all of its vertices. Recompute the bounds to avoid the mesh disappearing in game."#,
+            MeshExValidationError::BoundingSphereMissingVertices {
+                group_index: 0,
+                mesh_object_full_name: "mesh_VIS_O_OBJShape".to_string()
+            }
+            .to_string()
+        );
+    }
+
     #[test]
     fn format_nutexb_errors() {
         assert_eq!(
@@ -1315,6 +1765,42 @@ Use a Source Color of "One" or use a shader that does not premultiply alpha."#,
             }
             .to_string()
         );
+        assert_eq!(
+            r#"Texture "tex" has a size of 2.00 MB, which exceeds the budget of 1.00 MB."#,
+            NutexbValidationError::SizeExceedsBudget {
+                nutexb: "tex".to_string(),
+                size: 2 * 1024 * 1024,
+                budget: 1024 * 1024
+            }
+            .to_string()
+        );
+        assert_eq!(
+            r#"The textures in the folder have a total size of 2.00 MB, which exceeds the budget of 1.00 MB. Texture "tex" is the largest texture."#,
+            NutexbValidationError::FolderSizeExceedsBudget {
+                nutexb: "tex".to_string(),
+                size: 2 * 1024 * 1024,
+                budget: 1024 * 1024
+            }
+            .to_string()
+        );
+        assert_eq!(
+            r#"Texture "tex.nutexb" has 528 bytes of image data, but its dimensions, format, mipmaps, and layers require 512 bytes. The texture may fail to load in game."#,
+            NutexbValidationError::DataSizeTooSmall {
+                nutexb: "tex.nutexb".to_string(),
+                actual_size: 528,
+                expected_size: 512
+            }
+            .to_string()
+        );
+        assert_eq!(
+            r#"Texture "tex.nutexb" has 16 bytes of unneeded padding after its 512 bytes of image data."#,
+            NutexbValidationError::UnneededPadding {
+                nutexb: "tex.nutexb".to_string(),
+                actual_size: 528,
+                expected_size: 512
+            }
+            .to_string()
+        );
     }
 
     #[test]
@@ -2566,4 +3052,307 @@ Use a Source Color of "One" or use a shader that does not premultiply alpha."#,
             validation.mesh_errors[&0]
         );
     }
+
+    #[test]
+    fn mesh_budgets_exceeded() {
+        let mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![
+                MeshObjectData {
+                    name: "a".to_owned(),
+                    subindex: 0,
+                    positions: vec![AttributeData {
+                        name: "Position0".to_owned(),
+                        data: VectorData::Vector3(vec![[0.0; 3]; 3]),
+                    }],
+                    ..Default::default()
+                },
+                MeshObjectData {
+                    name: "b".to_owned(),
+                    subindex: 0,
+                    positions: vec![AttributeData {
+                        name: "Position0".to_owned(),
+                        data: VectorData::Vector3(vec![[0.0; 3]; 2]),
+                    }],
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let budgets = ModelBudgets {
+            mesh_vertex_count: Some(2),
+            folder_vertex_count: Some(4),
+            ..Default::default()
+        };
+
+        let mut validation = ModelFolderValidationErrors::default();
+        validate_mesh_budgets(&mut validation, 0, &mesh, &budgets);
+
+        assert_eq!(
+            vec![
+                MeshValidationError {
+                    mesh_object_index: 0,
+                    kind: MeshValidationErrorKind::VertexCountExceedsBudget {
+                        mesh_name: "a".to_owned(),
+                        vertex_count: 3,
+                        budget: 2
+                    }
+                },
+                MeshValidationError {
+                    mesh_object_index: 0,
+                    kind: MeshValidationErrorKind::FolderVertexCountExceedsBudget {
+                        mesh_name: "a".to_owned(),
+                        vertex_count: 5,
+                        budget: 4
+                    }
+                }
+            ],
+            validation.mesh_errors[&0]
+        );
+    }
+
+    #[test]
+    fn texture_budgets_exceeded() {
+        let mut a = nutexb(NutexbFormat::BC7Srgb);
+        a.footer.data_size = 8;
+        let mut b = nutexb(NutexbFormat::BC7Srgb);
+        b.footer.data_size = 4;
+        let nutexbs = vec![("a".to_owned(), Some(a)), ("b".to_owned(), Some(b))];
+
+        let budgets = ModelBudgets {
+            texture_size: Some(6),
+            folder_texture_size: Some(10),
+            ..Default::default()
+        };
+
+        let mut validation = ModelFolderValidationErrors::default();
+        validate_texture_budgets(&mut validation, &nutexbs, &budgets);
+
+        assert_eq!(
+            vec![
+                NutexbValidationError::SizeExceedsBudget {
+                    nutexb: "a".to_owned(),
+                    size: 8,
+                    budget: 6
+                },
+                NutexbValidationError::FolderSizeExceedsBudget {
+                    nutexb: "a".to_owned(),
+                    size: 12,
+                    budget: 10
+                }
+            ],
+            validation.nutexb_errors[&0]
+        );
+        assert!(!validation.nutexb_errors.contains_key(&1));
+    }
+
+    fn nutexb_with_data(width: u32, height: u32, mipmap_count: u32) -> NutexbFile {
+        let mut nutexb = nutexb(NutexbFormat::BC7Srgb);
+        nutexb.footer.string = "a".into();
+        nutexb.footer.width = width;
+        nutexb.footer.height = height;
+        nutexb.footer.mipmap_count = mipmap_count;
+        nutexb.data = vec![0u8; expected_data_size(&nutexb.footer)];
+        nutexb.footer.data_size = nutexb.data.len() as u32;
+        nutexb
+    }
+
+    #[test]
+    fn nutexb_footers_valid() {
+        let nutexbs = vec![("a.nutexb".to_owned(), Some(nutexb_with_data(64, 64, 7)))];
+
+        let mut validation = ModelFolderValidationErrors::default();
+        validate_nutexb_footers(&mut validation, &nutexbs);
+        assert!(validation.nutexb_errors.is_empty());
+    }
+
+    #[test]
+    fn nutexb_footers_data_size() {
+        let mut a = nutexb_with_data(64, 64, 1);
+        let expected_size = a.data.len();
+        a.data.extend_from_slice(&[0u8; 16]);
+
+        let mut b = nutexb_with_data(64, 64, 1);
+        b.footer.string = "b".into();
+        b.data.truncate(16);
+        b.footer.data_size = 16;
+
+        let nutexbs = vec![
+            ("a.nutexb".to_owned(), Some(a)),
+            ("b.nutexb".to_owned(), Some(b)),
+        ];
+
+        let mut validation = ModelFolderValidationErrors::default();
+        validate_nutexb_footers(&mut validation, &nutexbs);
+
+        assert_eq!(
+            vec![
+                NutexbValidationError::DataSizeMismatch {
+                    nutexb: "a.nutexb".to_owned(),
+                    data_size: expected_size,
+                    actual_size: expected_size + 16
+                },
+                NutexbValidationError::UnneededPadding {
+                    nutexb: "a.nutexb".to_owned(),
+                    actual_size: expected_size + 16,
+                    expected_size
+                }
+            ],
+            validation.nutexb_errors[&0]
+        );
+        assert_eq!(
+            vec![NutexbValidationError::DataSizeTooSmall {
+                nutexb: "b.nutexb".to_owned(),
+                actual_size: 16,
+                expected_size
+            }],
+            validation.nutexb_errors[&1]
+        );
+    }
+
+    #[test]
+    fn nutexb_footers_dimensions_and_name() {
+        let mut a = nutexb_with_data(64, 64, 1);
+        a.footer.mipmap_count = 8;
+
+        let b = nutexb_with_data(48, 64, 1);
+
+        let nutexbs = vec![
+            ("a.nutexb".to_owned(), Some(a)),
+            ("b.nutexb".to_owned(), Some(b)),
+        ];
+
+        let mut validation = ModelFolderValidationErrors::default();
+        validate_nutexb_footers(&mut validation, &nutexbs);
+
+        assert_eq!(
+            vec![NutexbValidationError::MipmapCountInvalid {
+                nutexb: "a.nutexb".to_owned(),
+                width: 64,
+                height: 64,
+                depth: 1,
+                mipmap_count: 8,
+                max_mipmap_count: 7
+            }],
+            validation.nutexb_errors[&0]
+        );
+        assert_eq!(
+            vec![
+                NutexbValidationError::CompressedSizeNotPowerOfTwo {
+                    nutexb: "b.nutexb".to_owned(),
+                    format: NutexbFormat::BC7Srgb,
+                    width: 48,
+                    height: 64
+                },
+                NutexbValidationError::NameMismatch {
+                    nutexb: "b.nutexb".to_owned(),
+                    name: "a".to_owned()
+                }
+            ],
+            validation.nutexb_errors[&1]
+        );
+    }
+
+    #[test]
+    fn format_xmb_errors() {
+        assert_eq!(
+            r#"Entry "lod" references "model_lod1.numdlb", but the file is not in the folder. Add the file or fix the reference to avoid missing LOD models in game."#,
+            XmbValidationError::MissingLodModel {
+                entry: "lod".to_owned(),
+                file_name: "model_lod1.numdlb".to_owned()
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn xmb_missing_lod_models() {
+        let entry = |name: &str, attributes: &[(&str, &str)], entries| XmbEntry {
+            name: name.to_owned(),
+            attributes: attributes
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            entries,
+        };
+        let xmb = XmbFile {
+            entries: vec![entry(
+                "model",
+                &[("name", "body")],
+                vec![
+                    entry("lod", &[("model", "model.numdlb")], Vec::new()),
+                    entry("lod", &[("model", "Model_LOD1.numdlb")], Vec::new()),
+                    entry("lod", &[("model", "model_lod2.numdlb")], Vec::new()),
+                ],
+            )],
+        };
+        let modls = vec![
+            ("model.numdlb".to_owned(), None),
+            ("model_lod1.numdlb".to_owned(), None),
+        ];
+
+        let mut validation = ModelFolderValidationErrors::default();
+        validate_xmb_lod_models(&mut validation, 0, &xmb, &modls);
+
+        assert_eq!(
+            vec![XmbValidationError::MissingLodModel {
+                entry: "lod".to_owned(),
+                file_name: "model_lod2.numdlb".to_owned()
+            }],
+            validation.xmb_errors[&0]
+        );
+    }
+
+    #[test]
+    fn meshex_bounds_missing_vertices() {
+        let mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![
+                MeshObjectData {
+                    name: "a".to_owned(),
+                    subindex: 0,
+                    positions: vec![AttributeData {
+                        name: "Position0".to_owned(),
+                        data: VectorData::Vector3(vec![[0.0, 0.5, 0.0]]),
+                    }],
+                    ..Default::default()
+                },
+                MeshObjectData {
+                    name: "b".to_owned(),
+                    subindex: 0,
+                    positions: vec![AttributeData {
+                        name: "Position0".to_owned(),
+                        data: VectorData::Vector3(vec![[0.0, 2.0, 0.0]]),
+                    }],
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let group = |name: &str| MeshObjectGroupData {
+            bounding_sphere: Vector4::new(0.0, 0.0, 0.0, 1.0),
+            mesh_object_full_name: name.to_owned(),
+            mesh_object_name: name.to_owned(),
+            entry_flags: vec![EntryFlags {
+                draw_model: true,
+                cast_shadow: true,
+            }],
+        };
+        let meshex = MeshExData {
+            mesh_object_groups: vec![group("a"), group("b")],
+        };
+
+        let mut validation = ModelFolderValidationErrors::default();
+        validate_meshex_bounds(&mut validation, 0, &meshex, &mesh);
+
+        assert_eq!(
+            vec![MeshExValidationError::BoundingSphereMissingVertices {
+                group_index: 1,
+                mesh_object_full_name: "b".to_owned()
+            }],
+            validation.meshex_errors[&0]
+        );
+    }
 }